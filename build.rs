@@ -1,16 +1,33 @@
 extern crate cmake;
 
+use std::env;
+use std::path::Path;
+
+#[path = "build/bindings.rs"]
+mod bindings;
+
 fn main() {
     let dst = cmake::build("lib/raylib");
 
     println!("cargo:rustc-link-search=native={}/lib", dst.display());
-    
+
     // Borrowed the list for Windows from raylib-rs
     // @see https://github.com/deltaphc/raylib-rs/blob/master/raylib-sys/build.rs#
     println!("cargo:rustc-link-lib=dylib=winmm");
     println!("cargo:rustc-link-lib=dylib=gdi32");
     println!("cargo:rustc-link-lib=dylib=user32");
     println!("cargo:rustc-link-lib=dylib=shell32");
-    
+
     println!("cargo:rustc-link-lib=static=raylib");
+
+    let api = "lib/raylib/parser/output/raylib_api.json";
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    println!("cargo:rerun-if-changed={}", api);
+    println!("cargo:rerun-if-changed=build/bindings.rs");
+    // Listing files above stops cargo rerunning on any change, so name our copy of raylib too.
+    println!("cargo:rerun-if-changed=lib/raylib/src");
+    println!("cargo:rerun-if-changed=lib/raylib/CMakeLists.txt");
+
+    bindings::generate(Path::new(api), Path::new(&out_dir));
 }
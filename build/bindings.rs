@@ -0,0 +1,843 @@
+//! Generates raylib bindings from `lib/raylib/parser/output/raylib_api.json`.
+//!
+//! Two files are written to `OUT_DIR`:
+//!
+//! - `raylib_ffi.rs`: structs, aliases, callbacks and the raw `extern "C"` block.
+//! - `raylib_safe.rs`: enums, defines and safe wrappers in the style of `src/raylib.rs`.
+//!   Drawing functions become default methods of the `RaylibDraw` trait, and
+//!   functions that need an open window become default methods of `RaylibWindow`.
+//!   Both traits are sealed by `sealed::Sealed`, which `src/raylib.rs` declares.
+//!
+//! Safe wrappers are only generated for functions whose parameters and return
+//! value can be passed without touching raw pointers. Everything else stays
+//! available through the `ffi` module.
+
+extern crate serde_json;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+/// Structs declared by hand in `src/raylib.rs`. The generated code reuses them.
+const HAND_WRITTEN_STRUCTS: &[&str] = &["Vector2", "Rectangle", "Color"];
+
+/// Functions that load or unload resources owned by the RAII wrappers in `src/raylib.rs`,
+/// such as `Texture`. Generated versions would hand out raw handles that are never freed,
+/// or close the audio device under the sounds still playing on it.
+const OWNED_RESOURCE_FUNCTIONS: &[&str] = &[
+    "InitAudioDevice", "CloseAudioDevice",
+    "LoadImage", "UnloadImage",
+    "LoadTexture", "LoadTextureFromImage", "LoadTextureCubemap", "UnloadTexture",
+    "LoadRenderTexture", "UnloadRenderTexture",
+    "LoadShader", "LoadShaderFromMemory", "UnloadShader",
+    "LoadFont", "LoadFontEx", "UnloadFont",
+    "LoadWave", "UnloadWave",
+    "LoadSound", "UnloadSound",
+    "LoadMusicStream", "UnloadMusicStream",
+];
+
+/// Symbols renamed in our copy of raylib to avoid clashes with the Win32 API.
+const LINK_NAMES: &[(&str, &str)] = &[("CloseWindow", "Raylib_CloseWindow")];
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "box", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while",
+];
+
+struct Param {
+    name: String,
+    c_type: String,
+}
+
+struct Field {
+    name: String,
+    c_type: String,
+    description: String,
+}
+
+struct Struct {
+    name: String,
+    description: String,
+    fields: Vec<Field>,
+}
+
+struct Alias {
+    name: String,
+    c_type: String,
+    description: String,
+}
+
+struct EnumValue {
+    name: String,
+    value: i64,
+    description: String,
+}
+
+struct Enum {
+    name: String,
+    description: String,
+    values: Vec<EnumValue>,
+}
+
+struct Define {
+    name: String,
+    kind: String,
+    value: Value,
+    description: String,
+}
+
+struct Function {
+    name: String,
+    description: String,
+    return_type: String,
+    params: Vec<Param>,
+}
+
+struct Api {
+    defines: Vec<Define>,
+    structs: Vec<Struct>,
+    aliases: Vec<Alias>,
+    enums: Vec<Enum>,
+    callbacks: Vec<Function>,
+    functions: Vec<Function>,
+}
+
+pub fn generate(api_path: &Path, out_dir: &Path) {
+    let text = fs::read_to_string(api_path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", api_path.display(), e));
+    let json: Value = serde_json::from_str(&text)
+        .unwrap_or_else(|e| panic!("failed to parse {}: {}", api_path.display(), e));
+
+    let api = Api::from_json(&json);
+    let generator = Generator::new(&api);
+
+    fs::write(out_dir.join("raylib_ffi.rs"), generator.ffi()).unwrap();
+    fs::write(out_dir.join("raylib_safe.rs"), generator.safe()).unwrap();
+}
+
+fn str_of(value: &Value, key: &str) -> String {
+    value[key].as_str().unwrap_or_default().to_string()
+}
+
+fn list_of<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value[key].as_array().map(|v| v.as_slice()).unwrap_or(&[])
+}
+
+impl Api {
+    fn from_json(json: &Value) -> Api {
+        let function = |f: &Value| Function {
+            name: str_of(f, "name"),
+            description: str_of(f, "description"),
+            return_type: str_of(f, "returnType"),
+            params: list_of(f, "params")
+                .iter()
+                .map(|p| Param {
+                    name: str_of(p, "name"),
+                    c_type: str_of(p, "type"),
+                })
+                .collect(),
+        };
+
+        Api {
+            defines: list_of(json, "defines")
+                .iter()
+                .map(|d| Define {
+                    name: str_of(d, "name"),
+                    kind: str_of(d, "type"),
+                    value: d["value"].clone(),
+                    description: str_of(d, "description"),
+                })
+                .collect(),
+            structs: list_of(json, "structs")
+                .iter()
+                .map(|s| Struct {
+                    name: str_of(s, "name"),
+                    description: str_of(s, "description"),
+                    fields: list_of(s, "fields")
+                        .iter()
+                        .map(|f| Field {
+                            name: str_of(f, "name"),
+                            c_type: str_of(f, "type"),
+                            description: str_of(f, "description"),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            aliases: list_of(json, "aliases")
+                .iter()
+                .map(|a| Alias {
+                    name: str_of(a, "name"),
+                    c_type: str_of(a, "type"),
+                    description: str_of(a, "description"),
+                })
+                .collect(),
+            enums: list_of(json, "enums")
+                .iter()
+                .map(|e| Enum {
+                    name: str_of(e, "name"),
+                    description: str_of(e, "description"),
+                    values: list_of(e, "values")
+                        .iter()
+                        .map(|v| EnumValue {
+                            name: str_of(v, "name"),
+                            value: v["value"].as_i64().unwrap_or_default(),
+                            description: str_of(v, "description"),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            callbacks: list_of(json, "callbacks").iter().map(function).collect(),
+            functions: list_of(json, "functions").iter().map(function).collect(),
+        }
+    }
+}
+
+/// How a C type crosses the boundary in a safe wrapper.
+enum SafeType {
+    /// Numbers and booleans, passed as is.
+    Primitive(String),
+    /// `const char *`, passed as `&str` with NUL bytes stripped.
+    Str,
+    /// A struct without raw pointers, passed by reference and cloned.
+    Plain(String),
+}
+
+struct Generator<'a> {
+    api: &'a Api,
+    aliases: HashMap<&'a str, &'a str>,
+    structs: HashMap<&'a str, &'a Struct>,
+    callbacks: HashSet<&'a str>,
+    /// Enum member names mapped to `(enum, variant)`.
+    enum_members: HashMap<&'a str, (&'a str, String)>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(api: &'a Api) -> Generator<'a> {
+        let mut enum_members = HashMap::new();
+        for e in &api.enums {
+            let variants = variant_names(e);
+            for (value, variant) in e.values.iter().zip(variants) {
+                enum_members.insert(value.name.as_str(), (e.name.as_str(), variant));
+            }
+        }
+
+        Generator {
+            api,
+            aliases: api.aliases.iter().map(|a| (a.name.as_str(), a.c_type.as_str())).collect(),
+            structs: api.structs.iter().map(|s| (s.name.as_str(), s)).collect(),
+            callbacks: api.callbacks.iter().map(|c| c.name.as_str()).collect(),
+            enum_members,
+        }
+    }
+
+    fn resolve<'b>(&'b self, name: &'b str) -> &'b str {
+        let mut name = name;
+        while let Some(target) = self.aliases.get(name) {
+            name = target;
+        }
+        name
+    }
+
+    /// Types that are neither primitives, structs, aliases nor callbacks, such as `rAudioBuffer`.
+    fn opaque_types(&self) -> Vec<String> {
+        let mut types = Vec::new();
+
+        let field_types = self.api.structs.iter().flat_map(|s| s.fields.iter().map(|f| &f.c_type));
+        let param_types = self
+            .api
+            .functions
+            .iter()
+            .chain(&self.api.callbacks)
+            .flat_map(|f| f.params.iter().map(|p| &p.c_type).chain(Some(&f.return_type)));
+
+        for c_type in field_types.chain(param_types) {
+            let (base, _, _) = split_pointer(c_type);
+            let base = base.split('[').next().unwrap_or(base);
+
+            if primitive_type(base).is_none()
+                && base != "void"
+                && base != "..."
+                && base != "va_list"
+                && !self.structs.contains_key(base)
+                && !self.aliases.contains_key(base)
+                && !self.callbacks.contains(base)
+                && !types.iter().any(|t| t == base)
+            {
+                types.push(base.to_string());
+            }
+        }
+
+        types
+    }
+
+    /// A struct is plain when neither it nor any struct it contains holds a raw pointer.
+    fn is_plain(&self, name: &str) -> bool {
+        let name = self.resolve(name);
+
+        match self.structs.get(name) {
+            Some(s) => s.fields.iter().all(|f| {
+                let (base, pointers, _) = split_pointer(&f.c_type);
+                let base = base.split('[').next().unwrap_or(base);
+
+                pointers == 0 && (primitive_type(base).is_some() || self.is_plain(base))
+            }),
+            None => false,
+        }
+    }
+
+    fn safe_type(&self, c_type: &str) -> Option<SafeType> {
+        if c_type == "const char *" {
+            return Some(SafeType::Str);
+        }
+
+        let (base, pointers, _) = split_pointer(c_type);
+
+        if pointers > 0 {
+            return None;
+        }
+
+        if let Some(rust) = primitive_type(base) {
+            return Some(SafeType::Primitive(rust.to_string()));
+        }
+
+        if self.is_plain(base) {
+            return Some(SafeType::Plain(base.to_string()));
+        }
+
+        None
+    }
+
+    fn ffi(&self) -> String {
+        let mut out = String::new();
+
+        writeln!(out, "// Generated by build.rs from raylib_api.json. Do not edit.").unwrap();
+        writeln!(out).unwrap();
+
+        for name in self.opaque_types() {
+            writeln!(out, "#[repr(C)]").unwrap();
+            writeln!(out, "pub struct {} {{", name).unwrap();
+            writeln!(out, "    _private: [u8; 0],").unwrap();
+            writeln!(out, "}}").unwrap();
+            writeln!(out).unwrap();
+        }
+
+        for s in &self.api.structs {
+            if HAND_WRITTEN_STRUCTS.contains(&s.name.as_str()) {
+                continue;
+            }
+
+            write_doc(&mut out, "", &s.description);
+            writeln!(out, "#[repr(C)]").unwrap();
+            writeln!(out, "#[derive(Clone)]").unwrap();
+            writeln!(out, "pub struct {} {{", s.name).unwrap();
+            for f in &s.fields {
+                write_doc(&mut out, "    ", &f.description);
+                writeln!(out, "    pub {}: {},", field_name(&f.name), ffi_type(&f.c_type)).unwrap();
+            }
+            writeln!(out, "}}").unwrap();
+            writeln!(out).unwrap();
+        }
+
+        for a in &self.api.aliases {
+            write_doc(&mut out, "", &a.description);
+            writeln!(out, "pub type {} = {};", a.name, a.c_type).unwrap();
+            writeln!(out).unwrap();
+        }
+
+        for c in &self.api.callbacks {
+            write_doc(&mut out, "", &c.description);
+            writeln!(
+                out,
+                "pub type {} = Option<unsafe extern \"C\" fn({}){}>;",
+                c.name,
+                ffi_params(&c.params),
+                ffi_return(&c.return_type),
+            )
+            .unwrap();
+            writeln!(out).unwrap();
+        }
+
+        writeln!(out, "#[link(name = \"raylib\", kind = \"static\")]").unwrap();
+        writeln!(out, "extern \"C\" {{").unwrap();
+        for f in &self.api.functions {
+            write_doc(&mut out, "    ", &f.description);
+            if let Some((_, link_name)) = LINK_NAMES.iter().find(|(name, _)| *name == f.name) {
+                writeln!(out, "    #[link_name = \"{}\"]", link_name).unwrap();
+            }
+            writeln!(
+                out,
+                "    pub fn {}({}){};",
+                f.name,
+                ffi_params(&f.params),
+                ffi_return(&f.return_type),
+            )
+            .unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+
+        out
+    }
+
+    fn safe(&self) -> String {
+        let mut out = String::new();
+
+        writeln!(out, "// Generated by build.rs from raylib_api.json. Do not edit.").unwrap();
+        writeln!(out).unwrap();
+
+        let mut types: Vec<&str> = self
+            .api
+            .structs
+            .iter()
+            .map(|s| s.name.as_str())
+            .filter(|name| !HAND_WRITTEN_STRUCTS.contains(name))
+            .chain(self.api.aliases.iter().map(|a| a.name.as_str()))
+            .collect();
+        types.sort_unstable();
+        writeln!(out, "pub use super::ffi::{{").unwrap();
+        for name in types {
+            writeln!(out, "    {},", name).unwrap();
+        }
+        writeln!(out, "}};").unwrap();
+        writeln!(out).unwrap();
+
+        for e in &self.api.enums {
+            self.write_enum(&mut out, e);
+        }
+
+        for d in &self.api.defines {
+            self.write_define(&mut out, d);
+        }
+        writeln!(out).unwrap();
+
+        let mut draw_methods = Vec::new();
+        let mut window_methods = Vec::new();
+
+        for f in &self.api.functions {
+            if is_scoped(&f.name) || OWNED_RESOURCE_FUNCTIONS.contains(&f.name.as_str()) {
+                continue;
+            }
+
+            if is_draw(&f.name) {
+                draw_methods.extend(self.wrapper(f, Some("&mut self")));
+            } else if needs_window(&f.name) {
+                window_methods.extend(self.wrapper(f, Some("&self")));
+            } else if let Some(lines) = self.wrapper(f, None) {
+                for line in lines {
+                    writeln!(out, "{}", line).unwrap();
+                }
+                writeln!(out).unwrap();
+            }
+        }
+
+        // Sealed, so only the handles in `src/raylib.rs` get these methods and they
+        // cannot be called without a window or outside a drawing scope.
+        writeln!(out, "/// Drawing functions, available on every drawing scope such as `DrawHandle`.").unwrap();
+        write_trait(&mut out, "RaylibDraw", &draw_methods);
+        writeln!(out).unwrap();
+        writeln!(out, "/// Window, timing and input functions, available on `RaylibHandle` once the window is open.").unwrap();
+        write_trait(&mut out, "RaylibWindow", &window_methods);
+
+        out
+    }
+
+    fn write_enum(&self, out: &mut String, e: &Enum) {
+        let variants = variant_names(e);
+        let mut seen: Vec<(i64, &str)> = Vec::new();
+        let mut duplicates = Vec::new();
+
+        write_doc(out, "", &e.description);
+        writeln!(out, "#[repr(i32)]").unwrap();
+        writeln!(out, "#[derive(Clone, Copy, Debug, PartialEq, Eq)]").unwrap();
+        writeln!(out, "pub enum {} {{", e.name).unwrap();
+        for (value, variant) in e.values.iter().zip(&variants) {
+            if let Some((_, first)) = seen.iter().find(|(v, _)| *v == value.value) {
+                duplicates.push((value, variant, *first));
+                continue;
+            }
+            seen.push((value.value, variant));
+
+            write_doc(out, "    ", &value.description);
+            writeln!(out, "    {} = {},", variant, value.value).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+        writeln!(out).unwrap();
+
+        // raylib hands enums back as plain ints, e.g. from GetKeyPressed().
+        writeln!(out, "impl TryFrom<i32> for {} {{", e.name).unwrap();
+        writeln!(out, "    type Error = i32;").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "    fn try_from(value: i32) -> Result<Self, i32> {{").unwrap();
+        writeln!(out, "        match value {{").unwrap();
+        for (value, variant) in &seen {
+            writeln!(out, "            {} => Ok({}::{}),", value, e.name, variant).unwrap();
+        }
+        writeln!(out, "            _ => Err(value),").unwrap();
+        writeln!(out, "        }}").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}").unwrap();
+        writeln!(out).unwrap();
+
+        // Rust enums cannot share a discriminant, so repeated values become constants.
+        if !duplicates.is_empty() {
+            writeln!(out, "#[allow(non_upper_case_globals)]").unwrap();
+            writeln!(out, "impl {} {{", e.name).unwrap();
+            for (value, variant, first) in duplicates {
+                write_doc(out, "    ", &value.description);
+                writeln!(out, "    pub const {}: {} = {}::{};", variant, e.name, e.name, first).unwrap();
+            }
+            writeln!(out, "}}").unwrap();
+            writeln!(out).unwrap();
+        }
+    }
+
+    fn write_define(&self, out: &mut String, d: &Define) {
+        let value = match d.kind.as_str() {
+            "INT" => format!("i32 = {}", d.value),
+            "FLOAT" => format!("f32 = {}", d.value),
+            "STRING" => format!("&str = {}", d.value),
+            "FLOAT_MATH" => format!("f32 = {}", strip_float_suffix(d.value.as_str().unwrap_or_default())),
+            "COLOR" => {
+                let literal = d.value.as_str().unwrap_or_default();
+                let inner = literal
+                    .split(['{', '}'])
+                    .nth(1)
+                    .unwrap_or_default();
+                let channels: Vec<&str> = inner.split(',').map(|c| c.trim()).collect();
+
+                format!(
+                    "Color = Color {{ r: {}, g: {}, b: {}, a: {} }}",
+                    channels[0], channels[1], channels[2], channels[3],
+                )
+            },
+            "UNKNOWN" => match self.enum_members.get(d.value.as_str().unwrap_or_default()) {
+                Some((name, variant)) => format!("{} = {}::{}", name, name, variant),
+                None => return,
+            },
+            _ => return,
+        };
+
+        write_doc(out, "", &d.description);
+        writeln!(out, "pub const {}: {};", d.name, value).unwrap();
+    }
+
+    /// Renders a safe wrapper, or `None` when `f` needs raw pointers.
+    /// With a `receiver` the wrapper becomes a trait method, so it can only be
+    /// called on the handle that implements the trait.
+    fn wrapper(&self, f: &Function, receiver: Option<&str>) -> Option<Vec<String>> {
+        let mut params = Vec::new();
+        let mut prelude = Vec::new();
+        let mut args = Vec::new();
+
+        if let Some(receiver) = receiver {
+            params.push(receiver.to_string());
+        }
+
+        for p in &f.params {
+            let name = field_name(&p.name);
+            let c_name = format!("c_{}", name.trim_start_matches("r#"));
+
+            match self.safe_type(&p.c_type)? {
+                SafeType::Primitive(rust) => {
+                    params.push(format!("{}: {}", name, rust));
+                    args.push(name);
+                },
+                SafeType::Str => {
+                    params.push(format!("{}: &str", name));
+                    prelude.push(format!("let {} = {}.to_c_str_lossy();", c_name, name));
+                    args.push(format!("{}.as_ptr()", c_name));
+                },
+                SafeType::Plain(rust) => {
+                    params.push(format!("{}: &{}", name, rust));
+                    prelude.push(format!("let {} = {}.clone();", c_name, name));
+                    args.push(c_name);
+                },
+            }
+        }
+
+        let call = format!("ffi::{}({})", f.name, args.join(", "));
+        let (ret, body) = match f.return_type.as_str() {
+            "void" => (String::new(), vec!["unsafe {".to_string(), format!("    {};", call), "}".to_string()]),
+            "const char *" => (
+                " -> String".to_string(),
+                vec![
+                    format!("let ptr = unsafe {{ {} }};", call),
+                    String::new(),
+                    "if ptr.is_null() {".to_string(),
+                    "    return String::new();".to_string(),
+                    "}".to_string(),
+                    String::new(),
+                    "unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned()".to_string(),
+                ],
+            ),
+            c_type => match self.safe_type(c_type)? {
+                SafeType::Primitive(rust) | SafeType::Plain(rust) => {
+                    (format!(" -> {}", rust), vec!["unsafe {".to_string(), format!("    {}", call), "}".to_string()])
+                },
+                SafeType::Str => return None,
+            },
+        };
+
+        let mut lines = Vec::new();
+        let description = f.description.trim();
+        if !description.is_empty() {
+            lines.push(format!("/// {}", description));
+        }
+        lines.push(format!(
+            "{}fn {}({}){} {{",
+            if receiver.is_some() { "" } else { "pub " },
+            snake_case(&f.name),
+            params.join(", "),
+            ret,
+        ));
+        for line in &prelude {
+            lines.push(format!("    {}", line));
+        }
+        if !prelude.is_empty() {
+            lines.push(String::new());
+        }
+        for line in body {
+            lines.push(if line.is_empty() { line } else { format!("    {}", line) });
+        }
+        lines.push("}".to_string());
+
+        Some(lines)
+    }
+}
+
+/// Window setup and `Begin*`/`End*` pairs are wrapped by hand as scope guards in `src/raylib.rs`.
+/// Pairs without a guard yet, such as `BeginMode3D`, stay in `ffi` rather than become
+/// unscoped safe functions.
+fn is_scoped(name: &str) -> bool {
+    name == "InitWindow" || name == "CloseWindow" || name.starts_with("Begin") || name.starts_with("End")
+}
+
+/// Functions that render into the current frame and so need a drawing scope.
+fn is_draw(name: &str) -> bool {
+    name == "ClearBackground" || name.starts_with("Draw")
+}
+
+/// Functions that only work once `InitWindow` has run: window, monitor, cursor,
+/// timing and input queries, and anything that touches the GPU.
+fn needs_window(name: &str) -> bool {
+    const PREFIXES: &[&str] = &[
+        "IsWindow", "SetWindow", "GetWindow", "GetRender", "GetMonitor",
+        "IsKey", "IsMouse", "GetMouse", "SetMouse", "IsGamepad", "GetGamepad", "SetGamepad",
+        "GetTouch", "IsGesture", "GetGesture", "GetWorldToScreen", "Load", "Unload",
+    ];
+    const NAMES: &[&str] = &[
+        "WindowShouldClose", "GetScreenWidth", "GetScreenHeight", "ClearWindowState", "ToggleFullscreen", "MaximizeWindow", "MinimizeWindow",
+        "RestoreWindow", "GetCurrentMonitor", "SetClipboardText", "GetClipboardText",
+        "EnableEventWaiting", "DisableEventWaiting", "SwapScreenBuffer", "PollInputEvents",
+        "ShowCursor", "HideCursor", "IsCursorHidden", "EnableCursor", "DisableCursor", "IsCursorOnScreen",
+        "SetTargetFPS", "GetFPS", "GetFrameTime", "GetTime", "TakeScreenshot", "IsFileDropped",
+        "SetExitKey", "GetKeyPressed", "GetCharPressed", "SetGesturesEnabled",
+        "SetShapesTexture", "SetTextureFilter", "SetTextureWrap", "MeasureText",
+    ];
+
+    NAMES.contains(&name) || PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+fn write_trait(out: &mut String, name: &str, methods: &[Vec<String>]) {
+    writeln!(out, "pub trait {}: sealed::Sealed {{", name).unwrap();
+    for (i, lines) in methods.iter().enumerate() {
+        if i > 0 {
+            writeln!(out).unwrap();
+        }
+        for line in lines {
+            if line.is_empty() {
+                writeln!(out).unwrap();
+            } else {
+                writeln!(out, "    {}", line).unwrap();
+            }
+        }
+    }
+    writeln!(out, "}}").unwrap();
+}
+
+fn write_doc(out: &mut String, indent: &str, description: &str) {
+    let description = description.trim();
+
+    if !description.is_empty() {
+        writeln!(out, "{}/// {}", indent, description).unwrap();
+    }
+}
+
+/// Splits `const char **` into `("char", 2, true)`.
+fn split_pointer(c_type: &str) -> (&str, usize, bool) {
+    let c_type = c_type.trim();
+    let pointers = c_type.chars().filter(|c| *c == '*').count();
+    let base = c_type.trim_end_matches(['*', ' ']);
+    let (base, is_const) = match base.strip_prefix("const ") {
+        Some(base) => (base, true),
+        None => (base, false),
+    };
+
+    (base, pointers, is_const)
+}
+
+fn primitive_type(c_type: &str) -> Option<&'static str> {
+    match c_type {
+        "bool" => Some("bool"),
+        "char" => Some("libc::c_char"),
+        "unsigned char" => Some("u8"),
+        "short" => Some("i16"),
+        "unsigned short" => Some("u16"),
+        "int" => Some("i32"),
+        "unsigned int" => Some("u32"),
+        "long" => Some("libc::c_long"),
+        "float" => Some("f32"),
+        "double" => Some("f64"),
+        _ => None,
+    }
+}
+
+fn ffi_type(c_type: &str) -> String {
+    if let Some(element) = c_type.strip_suffix(']') {
+        let (element, length) = element.split_once('[').unwrap();
+
+        return format!("[{}; {}]", ffi_type(element), length);
+    }
+
+    let (base, pointers, is_const) = split_pointer(c_type);
+
+    let mut rust = match base {
+        "void" if pointers > 0 => "libc::c_void".to_string(),
+        "void" => "()".to_string(),
+        "va_list" => "*mut libc::c_void".to_string(),
+        _ => primitive_type(base).map(str::to_string).unwrap_or_else(|| base.to_string()),
+    };
+
+    for i in 0..pointers {
+        rust = if i == 0 && is_const {
+            format!("*const {}", rust)
+        } else {
+            format!("*mut {}", rust)
+        };
+    }
+
+    rust
+}
+
+fn ffi_params(params: &[Param]) -> String {
+    params
+        .iter()
+        .map(|p| {
+            if p.c_type == "..." {
+                "...".to_string()
+            } else {
+                format!("{}: {}", field_name(&p.name), ffi_type(&p.c_type))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn ffi_return(c_type: &str) -> String {
+    if c_type == "void" {
+        String::new()
+    } else {
+        format!(" -> {}", ffi_type(c_type))
+    }
+}
+
+/// `InitWindow` -> `init_window`, `GetFPS` -> `get_fps`, `BeginMode2D` -> `begin_mode_2d`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if i > 0 {
+            let prev = chars[i - 1];
+            let next = chars.get(i + 1).copied();
+
+            let boundary = (c.is_ascii_uppercase() && (prev.is_ascii_lowercase()))
+                || (c.is_ascii_uppercase()
+                    && prev.is_ascii_uppercase()
+                    && next.is_some_and(|n| n.is_ascii_lowercase()))
+                || (c.is_ascii_digit()
+                    && prev.is_ascii_alphabetic()
+                    && next.is_some_and(|n| n.is_ascii_uppercase()));
+
+            if boundary {
+                out.push('_');
+            }
+        }
+
+        out.push(c.to_ascii_lowercase());
+    }
+
+    out
+}
+
+fn field_name(name: &str) -> String {
+    let name = snake_case(name);
+
+    if RUST_KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    } else {
+        name
+    }
+}
+
+/// `KEY_LEFT_SHIFT` in `KeyboardKey` -> `LeftShift`.
+fn variant_names(e: &Enum) -> Vec<String> {
+    let names: Vec<&str> = e.values.iter().map(|v| v.name.as_str()).collect();
+
+    let mut prefix = names.first().copied().unwrap_or_default();
+    for name in &names {
+        while !name.starts_with(prefix) {
+            prefix = &prefix[..prefix.len() - 1];
+        }
+    }
+    let prefix = match prefix.rfind('_') {
+        Some(i) => &prefix[..=i],
+        None => "",
+    };
+
+    names
+        .iter()
+        .map(|name| {
+            let mut rest = &name[prefix.len()..];
+
+            // Keep the last word of the prefix when the rest would start with a digit.
+            if rest.starts_with(|c: char| c.is_ascii_digit()) {
+                let start = prefix[..prefix.len() - 1].rfind('_').map_or(0, |i| i + 1);
+                rest = &name[start..];
+            }
+
+            rest.split('_')
+                .filter(|word| !word.is_empty())
+                .map(|word| {
+                    let mut chars = word.chars();
+                    let first = chars.next().unwrap().to_ascii_uppercase();
+
+                    first.to_string() + &chars.as_str().to_ascii_lowercase()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// `(PI/180.0f)` -> `PI/180.0`.
+fn strip_float_suffix(expression: &str) -> String {
+    let expression = expression
+        .strip_prefix('(')
+        .and_then(|e| e.strip_suffix(')'))
+        .unwrap_or(expression);
+    let mut out = String::new();
+    let mut prev = ' ';
+
+    for c in expression.chars() {
+        if !(c == 'f' && (prev.is_ascii_digit() || prev == '.')) {
+            out.push(c);
+        }
+        prev = c;
+    }
+
+    out
+}
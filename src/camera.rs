@@ -0,0 +1,128 @@
+use std::f32::consts::PI;
+
+use crate::raylib::{
+    Camera2D, Vector2,
+    get_random_value, get_screen_to_world_2d,
+};
+use crate::consts::{
+    MAX_SHAKE_OFFSET, MAX_SHAKE_ANGLE,
+};
+
+/// Seconds for a full shake to settle.
+const SHAKE_DECAY: f32 = 1.5;
+
+/// Camera over the playfield that can shake and zoom in briefly.
+///
+/// Shakes add "trauma" in `0.0..=1.0`, which decays over time. The camera moves by
+/// trauma squared, so small hits barely register while goals are felt.
+pub struct CameraController {
+    /// Point the camera looks at and keeps in the middle of the view.
+    center: Vector2,
+    shake_intensity: f32,
+    trauma: f32,
+    zoom: Option<Zoom>,
+    /// Shake is random, so the camera is rolled once per update and reused until the next.
+    camera: Camera2D,
+}
+
+struct Zoom {
+    amount: f32,
+    duration: f32,
+    elapsed: f32,
+}
+
+impl CameraController {
+    pub fn new(center: Vector2, shake_intensity: f32) -> Self {
+        Self {
+            center,
+            shake_intensity,
+            trauma: 0.0,
+            zoom: None,
+            camera: Camera2D {
+                offset: center,
+                target: center,
+                ..Camera2D::default()
+            },
+        }
+    }
+
+    /// Scales every shake. `0.0` turns shaking off.
+    pub fn set_shake_intensity(&mut self, intensity: f32) {
+        self.shake_intensity = intensity.max(0.0);
+    }
+
+    pub fn shake_intensity(&self) -> f32 {
+        self.shake_intensity
+    }
+
+    /// Adds to the current shake, `amount` being the fraction of a full shake.
+    pub fn shake(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    /// Zooms in by `amount` (0.2 is 20%) and back out over `duration` seconds.
+    pub fn zoom(&mut self, amount: f32, duration: f32) {
+        self.zoom = Some(Zoom {
+            amount,
+            duration,
+            elapsed: 0.0,
+        });
+    }
+
+    /// Stops any shake or zoom in progress.
+    pub fn reset(&mut self) {
+        self.trauma = 0.0;
+        self.zoom = None;
+        self.camera = self.next_camera();
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.trauma = (self.trauma - delta_time / SHAKE_DECAY).max(0.0);
+
+        if let Some(zoom) = &mut self.zoom {
+            zoom.elapsed += delta_time;
+
+            if zoom.elapsed >= zoom.duration {
+                self.zoom = None;
+            }
+        }
+
+        self.camera = self.next_camera();
+    }
+
+    pub fn camera(&self) -> &Camera2D {
+        &self.camera
+    }
+
+    fn next_camera(&self) -> Camera2D {
+        let shake = self.trauma * self.trauma * self.shake_intensity;
+
+        let offset = Vector2 {
+            x: MAX_SHAKE_OFFSET * shake * random_unit(),
+            y: MAX_SHAKE_OFFSET * shake * random_unit(),
+        };
+
+        // Eases in and back out along half a sine wave.
+        let zoom = match &self.zoom {
+            Some(zoom) => 1.0 + zoom.amount * (PI * zoom.elapsed / zoom.duration).sin(),
+            None => 1.0,
+        };
+
+        Camera2D {
+            offset: self.center,
+            target: self.center + offset,
+            rotation: MAX_SHAKE_ANGLE * shake * random_unit(),
+            zoom,
+        }
+    }
+
+    /// Maps a point on the canvas to the world under the camera.
+    pub fn screen_to_world(&self, point: &Vector2) -> Vector2 {
+        get_screen_to_world_2d(point, &self.camera)
+    }
+}
+
+/// A random value in `-1.0..=1.0`.
+fn random_unit() -> f32 {
+    get_random_value(-1000, 1000) as f32 / 1000.0
+}
@@ -0,0 +1,141 @@
+use crate::raylib::{
+    Rectangle, Vector2,
+    DrawHandle, RaylibHandle, RaylibWindow, RenderTexture, TextureFilter,
+    Key,
+    BLACK, WHITE,
+};
+use crate::effects::PostProcessing;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A fixed-size render target scaled into the window, keeping its aspect ratio
+/// and filling the rest with bars.
+///
+/// While a canvas is in use, raylib's mouse position is reported in canvas
+/// coordinates. F11 toggles fullscreen.
+pub struct Canvas {
+    target: RenderTexture,
+    /// Window size to restore when leaving fullscreen.
+    windowed_size: Option<(i32, i32)>,
+    /// Shared so scenes can toggle passes while the canvas runs them.
+    post_processing: Option<Rc<RefCell<PostProcessing>>>,
+}
+
+impl Canvas {
+    pub fn new(width: i32, height: i32) -> Option<Canvas> {
+        let target = RenderTexture::load(width, height)?;
+        target.set_filter(TextureFilter::Bilinear);
+
+        Some(Canvas {
+            target,
+            windowed_size: None,
+            post_processing: None,
+        })
+    }
+
+    /// Runs `post_processing` over every frame before it is scaled into the window.
+    pub fn set_post_processing(&mut self, post_processing: Rc<RefCell<PostProcessing>>) {
+        self.post_processing = Some(post_processing);
+    }
+
+    pub fn width(&self) -> i32 {
+        self.target.width()
+    }
+
+    pub fn height(&self) -> i32 {
+        self.target.height()
+    }
+
+    /// The largest centered rectangle in the window with the canvas' aspect ratio.
+    pub fn viewport(&self, rl: &RaylibHandle) -> Rectangle {
+        let screen_width = rl.get_screen_width() as f32;
+        let screen_height = rl.get_screen_height() as f32;
+
+        let scale = self.scale(rl);
+        let width = self.width() as f32 * scale;
+        let height = self.height() as f32 * scale;
+
+        Rectangle {
+            x: ((screen_width - width) / 2.0).floor(),
+            y: ((screen_height - height) / 2.0).floor(),
+            width,
+            height,
+        }
+    }
+
+    /// Window pixels per canvas pixel.
+    pub fn scale(&self, rl: &RaylibHandle) -> f32 {
+        let scale_x = rl.get_screen_width() as f32 / self.width() as f32;
+        let scale_y = rl.get_screen_height() as f32 / self.height() as f32;
+
+        scale_x.min(scale_y)
+    }
+
+    /// Maps a point in window coordinates into canvas coordinates. Points on
+    /// the bars fall outside `0..width` and `0..height`.
+    pub fn to_canvas(&self, rl: &RaylibHandle, point: &Vector2) -> Vector2 {
+        let viewport = self.viewport(rl);
+        let offset = Vector2 { x: viewport.x, y: viewport.y };
+
+        (*point - offset) / self.scale(rl)
+    }
+
+    /// The texture scenes draw into.
+    pub(crate) fn target(&self) -> &RenderTexture {
+        &self.target
+    }
+
+    pub(crate) fn target_mut(&mut self) -> &mut RenderTexture {
+        &mut self.target
+    }
+
+    /// Polls F11 and maps the mouse onto the canvas. Called once a frame, before the scenes update.
+    pub(crate) fn update(&mut self, rl: &RaylibHandle) {
+        if rl.is_key_pressed(Key::F11) {
+            self.toggle_fullscreen(rl);
+        }
+
+        let viewport = self.viewport(rl);
+        let scale = self.scale(rl);
+
+        rl.set_mouse_offset(-viewport.x as i32, -viewport.y as i32);
+        rl.set_mouse_scale(1.0 / scale, 1.0 / scale);
+    }
+
+    /// Switches to fullscreen at the monitor's resolution, so the canvas is
+    /// letterboxed instead of changing the video mode to the window size.
+    pub fn toggle_fullscreen(&mut self, rl: &RaylibHandle) {
+        if rl.is_window_fullscreen() {
+            rl.toggle_fullscreen();
+
+            if let Some((width, height)) = self.windowed_size.take() {
+                rl.set_window_size(width, height);
+            }
+        } else {
+            let monitor = rl.get_current_monitor();
+
+            self.windowed_size = Some((rl.get_screen_width(), rl.get_screen_height()));
+
+            rl.set_window_size(rl.get_monitor_width(monitor), rl.get_monitor_height(monitor));
+            rl.toggle_fullscreen();
+        }
+    }
+
+    pub(crate) fn post_process(&self, rl: &mut RaylibHandle) {
+        if let Some(post_processing) = &self.post_processing {
+            post_processing.borrow_mut().apply(rl, &self.target);
+        }
+    }
+
+    pub(crate) fn draw(&self, d: &mut DrawHandle) {
+        let post_processing = self.post_processing.as_ref().map(|post_processing| post_processing.borrow());
+        let output = post_processing.as_ref()
+            .and_then(|post_processing| post_processing.output())
+            .unwrap_or(&self.target);
+
+        d.clear_background(&BLACK);
+        let viewport = self.viewport(d);
+        d.draw_render_texture(output, &viewport, &WHITE);
+    }
+}
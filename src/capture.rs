@@ -0,0 +1,343 @@
+use crate::raylib::{
+    Color, Image,
+    DrawHandle,
+    Key,
+    flush_draw_batch,
+    RAYWHITE,
+};
+use crate::gif::GifEncoder;
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Saves screenshots (F9) and GIF clips (F10 to start and stop) into a
+/// directory, named after the UTC time they were taken, and shows a
+/// notice for a moment after each.
+pub struct Capture {
+    directory: PathBuf,
+    notice: Option<Notice>,
+    recording: Option<Recording>,
+}
+
+struct Notice {
+    text: String,
+    /// Seconds left on screen.
+    time_left: f32,
+}
+
+const NOTICE_DURATION: f32 = 2.5;
+
+/// A GIF clip being written, with a frame of the window taken every `GIF_INTERVAL`.
+/// Frames are encoded on a thread of their own, so the game does not stall on them.
+struct Recording {
+    frames: SyncSender<Frame>,
+    encoder: JoinHandle<io::Result<()>>,
+    path: String,
+    /// Of the clip, in pixels read back from the window.
+    width: i32,
+    height: i32,
+    /// The last frame taken, sent once it is known how long it shows.
+    pending: Vec<u8>,
+    /// Seconds the pending frame has been on screen.
+    shown: f32,
+    /// Seconds since the last frame was taken.
+    elapsed: f32,
+}
+
+/// RGBA pixels, and hundredths of a second they show for.
+type Frame = (Vec<u8>, u16);
+
+/// Seconds between frames taken, so clips run at 10 frames per second.
+const GIF_INTERVAL: f32 = 0.1;
+
+/// Frames that can wait for the encoder. Past that, frames are dropped and the
+/// frame before shows for longer, so the clip keeps to real time.
+const GIF_QUEUE: usize = 4;
+
+impl Recording {
+    /// Creates the file at `path` with the window's current contents as the first frame.
+    fn start(path: String) -> Option<Recording> {
+        let image = Image::from_screen()?;
+        let pending = image.rgba()?.to_vec();
+        let file = File::create(&path).ok()?;
+        let mut encoder = match GifEncoder::new(BufWriter::new(file), image.width(), image.height()) {
+            Ok(encoder) => encoder,
+            Err(_) => {
+                let _ = fs::remove_file(&path);
+
+                return None;
+            },
+        };
+
+        let (frames, queue) = mpsc::sync_channel::<Frame>(GIF_QUEUE);
+        let encoder = thread::spawn(move || {
+            for (rgba, delay) in queue {
+                encoder.frame(&rgba, delay)?;
+            }
+
+            encoder.finish().map(|_| ())
+        });
+
+        Some(Recording {
+            frames,
+            encoder,
+            path,
+            width: image.width(),
+            height: image.height(),
+            pending,
+            shown: 0.0,
+            elapsed: 0.0,
+        })
+    }
+
+    /// Takes the window's contents every `GIF_INTERVAL`. Frames of another size, after
+    /// the window was resized, are skipped. False once the encoder has failed.
+    fn update(&mut self, delta_time: f32) -> bool {
+        self.shown += delta_time;
+        self.elapsed += delta_time;
+        if self.elapsed < GIF_INTERVAL {
+            return true;
+        }
+        self.elapsed %= GIF_INTERVAL;
+
+        flush_draw_batch();
+        let rgba = match Image::from_screen() {
+            Some(image) if image.width() == self.width && image.height() == self.height => match image.rgba() {
+                Some(rgba) => rgba.to_vec(),
+                None => return false,
+            },
+            Some(_) => return true,
+            None => return false,
+        };
+
+        let delay = self.delay();
+        let pending = mem::replace(&mut self.pending, rgba);
+
+        match self.frames.try_send((pending, delay)) {
+            Ok(()) => {
+                // Whatever rounding left over goes to the next frame.
+                self.shown -= delay as f32 / 100.0;
+                true
+            },
+            Err(TrySendError::Full((pending, _))) => {
+                self.pending = pending;
+                true
+            },
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+
+    /// How long the pending frame has shown, in GIF time.
+    fn delay(&self) -> u16 {
+        (self.shown * 100.0).round().clamp(1.0, u16::MAX as f32) as u16
+    }
+
+    /// Sends the last frame and waits for the clip to be written.
+    fn finish(self) -> io::Result<()> {
+        let delay = self.delay();
+        // If the encoder has stopped, joining it gives the reason.
+        let _ = self.frames.send((self.pending, delay));
+        drop(self.frames);
+
+        self.encoder.join().unwrap_or_else(|_| Err(io::Error::other("GIF encoder panicked")))
+    }
+
+    /// Stops the encoder and removes what was written of the clip.
+    fn abandon(self) {
+        drop(self.frames);
+        let _ = self.encoder.join();
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+impl Capture {
+    pub fn new<P: Into<PathBuf>>(directory: P) -> Capture {
+        Capture {
+            directory: directory.into(),
+            notice: None,
+            recording: None,
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    pub fn set_directory<P: Into<PathBuf>>(&mut self, directory: P) {
+        self.directory = directory.into();
+    }
+
+    /// Polls the hotkeys and draws the notice. Called after the frame is drawn,
+    /// so screenshots do not include the notice.
+    pub(crate) fn frame(&mut self, d: &mut DrawHandle, delta_time: f32) {
+        if d.is_key_pressed(Key::F9) {
+            flush_draw_batch();
+            self.screenshot();
+        }
+
+        if d.is_key_pressed(Key::F10) {
+            flush_draw_batch();
+            self.toggle_gif();
+        } else if let Some(recording) = &mut self.recording {
+            if !recording.update(delta_time) {
+                self.fail_gif();
+            }
+        }
+
+        if let Some(notice) = &mut self.notice {
+            notice.time_left -= delta_time;
+
+            if notice.time_left <= 0.0 {
+                self.notice = None;
+            }
+        }
+
+        if let Some(notice) = &self.notice {
+            let width = d.measure_text(&notice.text, 20) + 20;
+
+            d.draw_rectangle(10, 10, width, 40, &Color { r: 0, g: 0, b: 0, a: 180 });
+            d.draw_text(&notice.text, 20, 20, 20, &RAYWHITE);
+        }
+    }
+
+    /// Saves what has been drawn to the window this frame as a PNG.
+    pub fn screenshot(&mut self) {
+        let saved = self.file_name("screenshot", "png").and_then(|path| {
+            let image = Image::from_screen()?;
+
+            image.export(&path).then_some(path)
+        });
+
+        self.notify(match saved {
+            Some(path) => format!("Saved {}", path),
+            None => "Screenshot failed".to_string(),
+        });
+    }
+
+    /// Starts recording what has been drawn to the window, or saves the recording.
+    pub fn toggle_gif(&mut self) {
+        let recording = match self.recording.take() {
+            Some(recording) => recording,
+            None => {
+                self.recording = self.file_name("clip", "gif").and_then(Recording::start);
+
+                return self.notify(match self.recording {
+                    Some(_) => "Recording GIF, F10 to stop".to_string(),
+                    None => "GIF recording failed".to_string(),
+                });
+            },
+        };
+
+        let path = recording.path.clone();
+
+        self.notify(match recording.finish() {
+            Ok(()) => format!("Saved {}", path),
+            Err(_) => {
+                let _ = fs::remove_file(&path);
+
+                "GIF recording failed".to_string()
+            },
+        });
+    }
+
+    /// Drops a recording that could not take a frame, with what was written of it.
+    fn fail_gif(&mut self) {
+        if let Some(recording) = self.recording.take() {
+            recording.abandon();
+        }
+
+        self.notify("GIF recording failed".to_string());
+    }
+
+    fn notify(&mut self, text: String) {
+        self.notice = Some(Notice {
+            text,
+            time_left: NOTICE_DURATION,
+        });
+    }
+
+    /// A path in the capture directory like `screenshot-20261018-153045.png`,
+    /// creating the directory if needed. A counter is added when the name is taken.
+    fn file_name(&self, prefix: &str, extension: &str) -> Option<String> {
+        fs::create_dir_all(&self.directory).ok()?;
+
+        let stem = format!("{}-{}", prefix, timestamp());
+        let mut path = self.directory.join(format!("{}.{}", stem, extension));
+        let mut counter = 1;
+
+        while path.exists() {
+            counter += 1;
+            path = self.directory.join(format!("{}-{}.{}", stem, counter, extension));
+        }
+
+        path.to_str().map(|path| path.to_string())
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        // Saves a clip still recording when the game quits.
+        if let Some(recording) = self.recording.take() {
+            let _ = recording.finish();
+        }
+    }
+}
+
+/// The current UTC time as `YYYYMMDD-HHMMSS`.
+fn timestamp() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    format_timestamp(seconds)
+}
+
+/// Seconds since the Unix epoch as a UTC `YYYYMMDD-HHMMSS`.
+fn format_timestamp(seconds: u64) -> String {
+    let days = seconds / 86400;
+    let time = seconds % 86400;
+
+    // Days since 1970-01-01 to a civil date, from Howard Hinnant's `civil_from_days`.
+    let z = days + 719468;
+    let era = z / 146097;
+    let day_of_era = z % 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year, month, day,
+        time / 3600, time % 3600 / 60, time % 60,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_timestamp;
+
+    #[test]
+    fn timestamp_of_the_epoch() {
+        assert_eq!(format_timestamp(0), "19700101-000000");
+    }
+
+    #[test]
+    fn timestamp_on_a_leap_day() {
+        assert_eq!(format_timestamp(1709210096), "20240229-123456");
+    }
+
+    #[test]
+    fn timestamp_across_a_year_boundary() {
+        assert_eq!(format_timestamp(1704067199), "20231231-235959");
+        assert_eq!(format_timestamp(1704067200), "20240101-000000");
+    }
+}
@@ -0,0 +1,225 @@
+use std::time::Instant;
+
+pub struct Time {
+    start: Instant,
+    old_elapsed: f32,
+}
+
+impl Default for Time {
+    fn default() -> Time {
+        Time {
+            start: Instant::now(),
+            old_elapsed: 0.0,
+        }
+    }
+}
+
+impl Time {
+    pub fn delta_time(&mut self) -> f32 {
+        let duration = self.start.elapsed();
+        let current = duration.as_secs_f32();
+        let delta = current - self.old_elapsed;
+
+        self.old_elapsed = current;
+
+        delta
+    }
+}
+
+/// Seconds covered by one update.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeltaTime {
+    /// Game time, after time scale, slow motion and pause. For gameplay.
+    pub scaled: f32,
+    /// Real time. For menus and other UI animation, which keep going at full speed.
+    pub unscaled: f32,
+}
+
+impl DeltaTime {
+    /// Both scaled and unscaled.
+    pub fn new(seconds: f32) -> DeltaTime {
+        DeltaTime {
+            scaled: seconds,
+            unscaled: seconds,
+        }
+    }
+}
+
+/// Game time layered on top of the real time `Time` measures: scaled, slowed down
+/// for a while, paused, or stepped one update at a time while paused.
+///
+/// `SceneManager` runs a global one, and scenes can keep their own on top of it.
+#[derive(Debug, Clone)]
+pub struct GameClock {
+    scale: f32,
+    paused: bool,
+    /// Updates left to run while paused.
+    steps: u32,
+    slow_motion: Option<SlowMotion>,
+}
+
+#[derive(Debug, Clone)]
+struct SlowMotion {
+    scale: f32,
+    /// Real seconds left.
+    time_left: f32,
+}
+
+impl Default for GameClock {
+    fn default() -> GameClock {
+        GameClock {
+            scale: 1.0,
+            paused: false,
+            steps: 0,
+            slow_motion: None,
+        }
+    }
+}
+
+impl GameClock {
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// `0.5` runs the game at half speed. Negative scales count as `0.0`.
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale.max(0.0);
+    }
+
+    /// Multiplies the scale by `scale` for `duration` real seconds, replacing any
+    /// slow motion in progress.
+    pub fn slow_motion(&mut self, scale: f32, duration: f32) {
+        self.slow_motion = Some(SlowMotion {
+            scale: scale.max(0.0),
+            time_left: duration,
+        });
+    }
+
+    pub fn in_slow_motion(&self) -> bool {
+        self.slow_motion.is_some()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// While paused, game time stands still but real time goes on.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        self.steps = 0;
+    }
+
+    /// Lets one update through while paused.
+    pub fn step(&mut self) {
+        if self.paused {
+            self.steps += 1;
+        }
+    }
+
+    /// Back to normal speed, unpaused.
+    pub fn reset(&mut self) {
+        *self = GameClock::default();
+    }
+
+    /// The time for one update, with this clock's scale applied on top of `delta_time.scaled`.
+    pub fn advance(&mut self, delta_time: DeltaTime) -> DeltaTime {
+        let mut scale = self.scale;
+
+        if let Some(slow_motion) = &mut self.slow_motion {
+            scale *= slow_motion.scale;
+            slow_motion.time_left -= delta_time.unscaled;
+
+            if slow_motion.time_left <= 0.0 {
+                self.slow_motion = None;
+            }
+        }
+
+        if self.paused {
+            if self.steps == 0 {
+                scale = 0.0;
+            } else {
+                self.steps -= 1;
+            }
+        }
+
+        DeltaTime {
+            scaled: delta_time.scaled * scale,
+            unscaled: delta_time.unscaled,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeltaTime, GameClock};
+
+    #[test]
+    fn scale_applies_to_scaled_time_only() {
+        let mut clock = GameClock::default();
+        clock.set_scale(0.5);
+
+        assert_eq!(clock.advance(DeltaTime::new(0.25)), DeltaTime { scaled: 0.125, unscaled: 0.25 });
+
+        clock.set_scale(-1.0);
+        assert_eq!(clock.advance(DeltaTime::new(0.25)).scaled, 0.0);
+    }
+
+    #[test]
+    fn slow_motion_expires_after_its_real_time() {
+        let mut clock = GameClock::default();
+        clock.set_scale(0.5);
+        clock.slow_motion(0.5, 0.5);
+
+        assert_eq!(clock.advance(DeltaTime::new(0.25)).scaled, 0.0625);
+        assert!(clock.in_slow_motion());
+
+        // Real time runs out even when no game time goes by.
+        let frozen = DeltaTime { scaled: 0.0, unscaled: 0.25 };
+        assert_eq!(clock.advance(frozen).scaled, 0.0);
+        assert!(!clock.in_slow_motion());
+
+        assert_eq!(clock.advance(DeltaTime::new(0.25)).scaled, 0.125);
+    }
+
+    #[test]
+    fn paused_clock_stands_still() {
+        let mut clock = GameClock::default();
+        clock.set_paused(true);
+
+        assert_eq!(clock.advance(DeltaTime::new(0.25)), DeltaTime { scaled: 0.0, unscaled: 0.25 });
+
+        clock.set_paused(false);
+        assert_eq!(clock.advance(DeltaTime::new(0.25)).scaled, 0.25);
+    }
+
+    #[test]
+    fn step_lets_one_update_through_while_paused() {
+        let mut clock = GameClock::default();
+        clock.set_scale(0.5);
+        clock.set_paused(true);
+        clock.step();
+
+        assert_eq!(clock.advance(DeltaTime::new(0.25)).scaled, 0.125);
+        assert_eq!(clock.advance(DeltaTime::new(0.25)).scaled, 0.0);
+    }
+
+    #[test]
+    fn step_does_nothing_while_running() {
+        let mut clock = GameClock::default();
+        clock.step();
+        clock.set_paused(true);
+
+        assert_eq!(clock.advance(DeltaTime::new(0.25)).scaled, 0.0);
+    }
+
+    #[test]
+    fn set_paused_clears_pending_steps() {
+        let mut clock = GameClock::default();
+        clock.set_paused(true);
+        clock.step();
+        clock.step();
+        clock.set_paused(true);
+
+        assert_eq!(clock.advance(DeltaTime::new(0.25)).scaled, 0.0);
+    }
+}
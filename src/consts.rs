@@ -1,11 +1,33 @@
 use crate::raylib::Color;
 
+/// Size of the virtual canvas the game is laid out on, not of the window.
 pub const SCREEN_WIDTH: i32 = 450;
 pub const SCREEN_HEIGHT: i32 = 800;
 pub const BRICKS_PER_LINE: i32 = 6;
 pub const BRICK_HEIGHT: f32 = 20.0;
 pub const PLAYER_SPEED: f32 = 300.0;
 pub const BALL_SPEED: f32 = 600.0;
+pub const GAMEPAD_DEADZONE: f32 = 0.2;
+pub const MAX_GAMEPADS: usize = 4;
+pub const SKINS_DIR: &str = "assets/skins";
+pub const FONT_PATH: &str = "assets/fonts/score.ttf";
+/// Where F9 screenshots and F10 GIF clips are saved.
+pub const CAPTURE_DIR: &str = "captures";
+/// Names the scene to start in when `--scene` is not given.
+pub const SCENE_ENV: &str = "PONG_SCENE";
+pub const WINNING_SCORE: i32 = 7;
+/// Scales camera shake, 0.0 turns it off. Toggled in game with [V].
+pub const SHAKE_INTENSITY: f32 = 1.0;
+pub const MAX_SHAKE_OFFSET: f32 = 12.0;
+/// Degrees.
+pub const MAX_SHAKE_ANGLE: f32 = 2.0;
+pub const MAX_PARTICLES: usize = 512;
+/// Game speed while the ball nears a goal that would end the match.
+pub const SLOW_MOTION_SCALE: f32 = 0.3;
+/// Pixels from the goal line where slow motion starts.
+pub const SLOW_MOTION_DISTANCE: f32 = 120.0;
+/// Afterimages drawn behind the ball, 0 turns the trail off.
+pub const BALL_TRAIL_LENGTH: usize = 10;
 
 pub const ELEGANT_BLACK: Color = Color { r: 19, g: 19, b: 18, a: 255 };
 pub const PADDLE_GRAY: Color = Color { r: 230, g: 230, b: 230, a: 255 };
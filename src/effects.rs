@@ -0,0 +1,216 @@
+use crate::raylib::{
+    Rectangle, Vector2,
+    RaylibHandle, RenderTexture, Shader, TextureFilter,
+    BLANK, WHITE,
+};
+
+pub const CRT: &str = "CRT scanlines";
+pub const BLOOM: &str = "Bloom";
+pub const VIGNETTE: &str = "Vignette";
+
+// GLSL 330, which raylib uses on desktop OpenGL 3.3. raylib's default vertex
+// shader feeds these the attributes and uniforms declared below.
+
+const BLOOM_FS: &str = r#"#version 330
+
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+uniform vec2 resolution;
+
+out vec4 finalColor;
+
+const int RANGE = 2;
+const float SPREAD = 2.5;
+const float THRESHOLD = 0.6;
+
+void main() {
+    vec4 source = texture(texture0, fragTexCoord);
+    vec2 texel = SPREAD / resolution;
+    vec3 glow = vec3(0.0);
+
+    for (int x = -RANGE; x <= RANGE; x++) {
+        for (int y = -RANGE; y <= RANGE; y++) {
+            vec3 color = texture(texture0, fragTexCoord + vec2(x, y) * texel).rgb;
+            glow += max(color - THRESHOLD, 0.0);
+        }
+    }
+
+    glow /= float((2 * RANGE + 1) * (2 * RANGE + 1));
+
+    finalColor = vec4(source.rgb + glow * 2.0, source.a) * fragColor * colDiffuse;
+}
+"#;
+
+const CRT_FS: &str = r#"#version 330
+
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+uniform vec2 resolution;
+
+out vec4 finalColor;
+
+const float SCANLINE_DEPTH = 0.25;
+
+void main() {
+    vec4 color = texture(texture0, fragTexCoord);
+
+    // Darkens every other row of canvas pixels.
+    float scanline = abs(sin(fragTexCoord.y * resolution.y * 3.14159265));
+    color.rgb *= 1.0 - SCANLINE_DEPTH * (1.0 - scanline);
+
+    finalColor = color * fragColor * colDiffuse;
+}
+"#;
+
+const VIGNETTE_FS: &str = r#"#version 330
+
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+
+out vec4 finalColor;
+
+const float RADIUS = 0.75;
+const float SOFTNESS = 0.45;
+
+void main() {
+    vec4 color = texture(texture0, fragTexCoord);
+
+    float distance = length(fragTexCoord - vec2(0.5));
+    color.rgb *= smoothstep(RADIUS, RADIUS - SOFTNESS, distance);
+
+    finalColor = color * fragColor * colDiffuse;
+}
+"#;
+
+/// A chain of full-screen shader passes, each reading the previous one's result.
+///
+/// Passes ping-pong between two render textures the size of the source.
+pub struct PostProcessing {
+    passes: Vec<PostPass>,
+    buffers: [RenderTexture; 2],
+    /// The buffer holding the last result, `None` when every pass is off.
+    output: Option<usize>,
+}
+
+pub struct PostPass {
+    name: String,
+    shader: Shader,
+    enabled: bool,
+}
+
+impl PostPass {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn shader(&self) -> &Shader {
+        &self.shader
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+impl PostProcessing {
+    pub fn new(width: i32, height: i32) -> Option<PostProcessing> {
+        let buffers = [RenderTexture::load(width, height)?, RenderTexture::load(width, height)?];
+
+        for buffer in &buffers {
+            buffer.set_filter(TextureFilter::Bilinear);
+        }
+
+        Some(PostProcessing {
+            passes: Vec::new(),
+            buffers,
+            output: None,
+        })
+    }
+
+    /// Appends an enabled pass. Passes run in the order they were added.
+    pub fn add(&mut self, name: &str, shader: Shader) {
+        self.passes.push(PostPass {
+            name: name.to_string(),
+            shader,
+            enabled: true,
+        });
+    }
+
+    pub fn passes(&self) -> &[PostPass] {
+        &self.passes
+    }
+
+    pub fn passes_mut(&mut self) -> &mut [PostPass] {
+        &mut self.passes
+    }
+
+    pub fn pass_mut(&mut self, name: &str) -> Option<&mut PostPass> {
+        self.passes.iter_mut().find(|pass| pass.name == name)
+    }
+
+    /// Runs every enabled pass over `source`. Call this outside `begin_drawing`.
+    pub fn apply(&mut self, rl: &mut RaylibHandle, source: &RenderTexture) {
+        self.output = None;
+
+        for pass in self.passes.iter().filter(|pass| pass.enabled) {
+            let (first, second) = self.buffers.split_at_mut(1);
+            let (input, target, output) = match self.output {
+                None => (source, &mut first[0], 0),
+                Some(0) => (&first[0], &mut second[0], 1),
+                Some(_) => (&second[0], &mut first[0], 0),
+            };
+
+            let dest = Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: target.width() as f32,
+                height: target.height() as f32,
+            };
+
+            let mut d = rl.begin_texture_mode(target);
+            d.clear_background(&BLANK);
+
+            d.begin_shader_mode(&pass.shader).draw_render_texture(input, &dest, &WHITE);
+
+            self.output = Some(output);
+        }
+    }
+
+    /// The result of the last `apply`, `None` when it had no passes to run.
+    pub fn output(&self) -> Option<&RenderTexture> {
+        self.output.map(|index| &self.buffers[index])
+    }
+}
+
+/// Bloom, then CRT scanlines, then vignette. CRT starts off. Passes whose
+/// shader cannot be loaded are left out.
+pub fn load_post_processing(width: i32, height: i32) -> Option<PostProcessing> {
+    let mut post_processing = PostProcessing::new(width, height)?;
+    let resolution = Vector2 { x: width as f32, y: height as f32 };
+
+    for (name, code) in [(BLOOM, BLOOM_FS), (CRT, CRT_FS), (VIGNETTE, VIGNETTE_FS)] {
+        if let Some(shader) = Shader::load_from_memory(None, Some(code)) {
+            shader.set_uniform("resolution", resolution);
+            post_processing.add(name, shader);
+        }
+    }
+
+    if let Some(crt) = post_processing.pass_mut(CRT) {
+        crt.set_enabled(false);
+    }
+
+    Some(post_processing)
+}
@@ -0,0 +1,358 @@
+use std::io::{self, Write};
+
+/// Writes an animated GIF one frame at a time, looping forever.
+///
+/// Colors are reduced to a fixed palette of 3 bits of red and green and 2 of
+/// blue. That is fast and needs no pass over the whole clip, which is enough
+/// for recordings of the game.
+pub struct GifEncoder<W: Write> {
+    writer: W,
+    width: u16,
+    height: u16,
+    /// Palette indices of the frame being encoded, kept to reuse the allocation.
+    indices: Vec<u8>,
+    table: CodeTable,
+}
+
+const MIN_CODE_SIZE: u8 = 8;
+const CLEAR_CODE: u16 = 1 << MIN_CODE_SIZE;
+const END_CODE: u16 = CLEAR_CODE + 1;
+/// GIF codes are at most 12 bits.
+const MAX_CODE: u16 = 4095;
+
+impl<W: Write> GifEncoder<W> {
+    /// Writes the header for frames of `width` by `height` pixels.
+    pub fn new(mut writer: W, width: i32, height: i32) -> io::Result<GifEncoder<W>> {
+        let (width, height) = match (u16::try_from(width), u16::try_from(height)) {
+            (Ok(width), Ok(height)) if width > 0 && height > 0 => (width, height),
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, "GIF frames must be 1 to 65535 pixels across")),
+        };
+
+        writer.write_all(b"GIF89a")?;
+        writer.write_all(&width.to_le_bytes())?;
+        writer.write_all(&height.to_le_bytes())?;
+        // A global color table of 256 colors, 8 bits per channel.
+        writer.write_all(&[0xF7, 0, 0])?;
+        for index in 0..=255 {
+            writer.write_all(&palette_color(index))?;
+        }
+
+        // The NETSCAPE2.0 extension, looping forever.
+        writer.write_all(&[0x21, 0xFF, 0x0B])?;
+        writer.write_all(b"NETSCAPE2.0")?;
+        writer.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+
+        Ok(GifEncoder {
+            writer,
+            width,
+            height,
+            indices: Vec::new(),
+            table: CodeTable::new(),
+        })
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width as i32
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height as i32
+    }
+
+    /// Appends a frame shown for `delay` hundredths of a second. `rgba` is the
+    /// frame's pixels row by row from the top, 4 bytes each; alpha is ignored.
+    pub fn frame(&mut self, rgba: &[u8], delay: u16) -> io::Result<()> {
+        if rgba.len() != self.width as usize * self.height as usize * 4 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "frame size does not match the GIF"));
+        }
+
+        self.indices.clear();
+        self.indices.extend(rgba.chunks_exact(4).map(|pixel| palette_index(pixel[0], pixel[1], pixel[2])));
+
+        // Graphic control extension, for the delay.
+        self.writer.write_all(&[0x21, 0xF9, 0x04, 0x00])?;
+        self.writer.write_all(&delay.to_le_bytes())?;
+        self.writer.write_all(&[0x00, 0x00])?;
+
+        // Image descriptor covering the whole GIF, with the global color table.
+        self.writer.write_all(&[0x2C, 0, 0, 0, 0])?;
+        self.writer.write_all(&self.width.to_le_bytes())?;
+        self.writer.write_all(&self.height.to_le_bytes())?;
+        self.writer.write_all(&[0x00, MIN_CODE_SIZE])?;
+
+        for block in lzw_encode(&self.indices, &mut self.table).chunks(255) {
+            self.writer.write_all(&[block.len() as u8])?;
+            self.writer.write_all(block)?;
+        }
+
+        self.writer.write_all(&[0x00])
+    }
+
+    /// Writes the trailer and gives the writer back.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.write_all(&[0x3B])?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+fn palette_index(r: u8, g: u8, b: u8) -> u8 {
+    (r & 0xE0) | ((g & 0xE0) >> 3) | (b >> 6)
+}
+
+fn palette_color(index: u8) -> [u8; 3] {
+    let scale = |value: u8, max: u8| (value as u32 * 255 / max as u32) as u8;
+
+    [scale(index >> 5, 7), scale((index >> 2) & 7, 7), scale(index & 3, 3)]
+}
+
+/// The codes of the strings seen so far, each found from the code of the string
+/// without its last index and that index.
+struct CodeTable {
+    /// Indexed by `prefix * 256 + index`. 0 is no code: codes below `END_CODE` are
+    /// single indices, which never have a prefix.
+    codes: Box<[u16]>,
+    /// The slots written since the last `clear`, so it does not go over them all.
+    used: Vec<usize>,
+}
+
+impl CodeTable {
+    fn new() -> CodeTable {
+        CodeTable {
+            codes: vec![0; (MAX_CODE as usize + 1) * 256].into_boxed_slice(),
+            used: Vec::new(),
+        }
+    }
+
+    fn get(&self, prefix: u16, index: u8) -> Option<u16> {
+        match self.codes[prefix as usize * 256 + index as usize] {
+            0 => None,
+            code => Some(code),
+        }
+    }
+
+    fn insert(&mut self, prefix: u16, index: u8, code: u16) {
+        let slot = prefix as usize * 256 + index as usize;
+
+        self.codes[slot] = code;
+        self.used.push(slot);
+    }
+
+    fn clear(&mut self) {
+        for slot in self.used.drain(..) {
+            self.codes[slot] = 0;
+        }
+    }
+}
+
+/// Compresses palette indices into GIF's variable-width LZW codes, packed
+/// least significant bit first.
+fn lzw_encode(indices: &[u8], table: &mut CodeTable) -> Vec<u8> {
+    let mut out = BitWriter::default();
+    let mut code_size = MIN_CODE_SIZE + 1;
+    let mut next_code = END_CODE + 1;
+
+    table.clear();
+    out.write(CLEAR_CODE, code_size);
+
+    let mut indices = indices.iter();
+    let mut prefix = match indices.next() {
+        Some(&index) => index as u16,
+        None => {
+            out.write(END_CODE, code_size);
+            return out.finish();
+        },
+    };
+
+    for &index in indices {
+        if let Some(code) = table.get(prefix, index) {
+            prefix = code;
+            continue;
+        }
+
+        out.write(prefix, code_size);
+
+        if next_code <= MAX_CODE {
+            table.insert(prefix, index, next_code);
+
+            // Decoders add each entry one code later, and widen their codes once
+            // the entry before this one fills the current width.
+            if next_code == 1 << code_size {
+                code_size += 1;
+            }
+            next_code += 1;
+        } else {
+            out.write(CLEAR_CODE, code_size);
+
+            table.clear();
+            code_size = MIN_CODE_SIZE + 1;
+            next_code = END_CODE + 1;
+        }
+
+        prefix = index as u16;
+    }
+
+    out.write(prefix, code_size);
+
+    // Reading that last code, decoders add one more entry, which can widen the end code.
+    if next_code == 1 << code_size {
+        code_size += 1;
+    }
+    out.write(END_CODE, code_size);
+
+    out.finish()
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lzw_encode, palette_color, palette_index, CodeTable, GifEncoder, CLEAR_CODE, END_CODE, MAX_CODE, MIN_CODE_SIZE};
+
+    /// Decodes LZW codes the way giflib does.
+    fn lzw_decode(data: &[u8]) -> Vec<u8> {
+        let mut table: Vec<Vec<u8>> = (0..=END_CODE).map(|code| vec![code as u8]).collect();
+        let mut code_size = MIN_CODE_SIZE + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut out = Vec::new();
+        let mut position = 0;
+
+        loop {
+            let mut code = 0;
+            for bit in 0..code_size as usize {
+                let byte = data[(position + bit) / 8];
+                code |= (((byte >> ((position + bit) % 8)) & 1) as usize) << bit;
+            }
+            position += code_size as usize;
+
+            if code == CLEAR_CODE as usize {
+                table.truncate(END_CODE as usize + 1);
+                code_size = MIN_CODE_SIZE + 1;
+                previous = None;
+                continue;
+            }
+
+            if code == END_CODE as usize {
+                return out;
+            }
+
+            let entry = match table.get(code) {
+                Some(entry) => entry.clone(),
+                None => {
+                    let mut entry = previous.clone().unwrap();
+                    entry.push(entry[0]);
+                    entry
+                },
+            };
+            out.extend_from_slice(&entry);
+
+            if let Some(mut previous) = previous {
+                if table.len() <= MAX_CODE as usize {
+                    previous.push(entry[0]);
+                    table.push(previous);
+
+                    if table.len() == 1 << code_size && code_size < 12 {
+                        code_size += 1;
+                    }
+                }
+            }
+
+            previous = Some(entry);
+        }
+    }
+
+    /// Noisy indices from a small xorshift, hard to compress so the table fills up.
+    fn noise(length: usize, levels: u32) -> Vec<u8> {
+        let mut state = 2463534242u32;
+
+        (0..length).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+
+            (state % levels) as u8
+        }).collect()
+    }
+
+    #[test]
+    fn lzw_round_trips_short_input() {
+        for indices in [vec![], vec![7], vec![1, 1, 1, 1, 1, 1, 1], vec![0, 1, 0, 1, 0, 1, 2, 255]] {
+            assert_eq!(lzw_decode(&lzw_encode(&indices, &mut CodeTable::new())), indices);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trips_through_code_widths() {
+        // Each length ends right around a point where codes widen.
+        for length in [250, 251, 252, 253, 254, 255, 256, 257, 258, 259, 260, 510, 511, 512, 513, 514] {
+            let indices = noise(length, 256);
+
+            assert_eq!(lzw_decode(&lzw_encode(&indices, &mut CodeTable::new())), indices, "length {}", length);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trips_past_a_full_table() {
+        let mut table = CodeTable::new();
+
+        // One table for all, as for the frames of a GIF.
+        for levels in [2, 16, 256] {
+            let indices = noise(200_000, levels);
+
+            assert_eq!(lzw_decode(&lzw_encode(&indices, &mut table)), indices, "{} levels", levels);
+        }
+    }
+
+    #[test]
+    fn palette_keeps_the_top_bits() {
+        assert_eq!(palette_color(palette_index(0, 0, 0)), [0, 0, 0]);
+        assert_eq!(palette_color(palette_index(255, 255, 255)), [255, 255, 255]);
+        assert_eq!(palette_color(palette_index(255, 0, 0)), [255, 0, 0]);
+        assert_eq!(palette_color(palette_index(0, 0, 255)), [0, 0, 255]);
+    }
+
+    #[test]
+    fn encoder_writes_a_complete_gif() {
+        let mut encoder = GifEncoder::new(Vec::new(), 2, 1).unwrap();
+        encoder.frame(&[255, 0, 0, 255, 0, 0, 255, 255], 10).unwrap();
+        let gif = encoder.finish().unwrap();
+
+        assert!(gif.starts_with(b"GIF89a\x02\x00\x01\x00"));
+        assert_eq!(gif.last(), Some(&0x3B));
+    }
+
+    #[test]
+    fn encoder_rejects_frames_of_another_size() {
+        let mut encoder = GifEncoder::new(Vec::new(), 2, 2).unwrap();
+
+        assert!(encoder.frame(&[0; 8], 10).is_err());
+        assert!(GifEncoder::new(Vec::new(), 0, 2).is_err());
+    }
+}
@@ -1,57 +1,173 @@
 use crate::raylib::{
-    Vector2, Color, Scene, SceneResult, Rectangle,
+    Vector2, Color, Rectangle,
     GRAY, DARKGRAY, MAROON,
-    is_key_pressed, Key,
-    clear_background, draw_text, draw_rectangle,
-    measure_text,
+    Key, GamepadButton,
+    DrawHandle, RaylibHandle, RaylibWindow,
+    Font, TextureFilter,
+    CachedText,
 };
 use crate::consts::{
     SCREEN_HEIGHT, SCREEN_WIDTH,
     BRICK_HEIGHT,
     BRICKS_PER_LINE,
     ELEGANT_BLACK,
+    MAX_GAMEPADS,
+    SKINS_DIR,
+    FONT_PATH,
+    WINNING_SCORE,
+    SHAKE_INTENSITY,
+    MAX_PARTICLES,
+    SLOW_MOTION_SCALE, SLOW_MOTION_DISTANCE,
 };
 use crate::objects::{
-    Player, Enemy, Ball, Brick, Turn, 
+    Player, Enemy, Ball, Brick, Turn, Control, Skins,
     draw_player, draw_ball, draw_enemy, draw_brick,
     move_player, move_enemy, move_ball,
     init_ball, collide_ball,
 };
+use crate::sounds::{Sounds, Effect};
+use crate::camera::CameraController;
+use crate::clock::{DeltaTime, GameClock};
+use crate::effects::PostProcessing;
+use crate::scene::{Scene, SceneResult};
+use crate::levels::overlays::{PauseMenu, MatchResult};
+use crate::renderer::Renderer;
+use crate::particles::{
+    Particles, Emitter,
+    BRICK_BURST, PADDLE_SPARKS, GOAL_BURST,
+};
+
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::mem;
+use std::path::Path;
+use std::rc::Rc;
 
-enum GameResult {
+#[derive(Clone, Copy)]
+pub enum GameResult {
     Undetermined,
     PlayerWin,
     EnemyWin,
 }
 
+/// What `Level0` draws and plays. Kept across restarts.
+pub struct Assets {
+    pub skins: Skins,
+    pub sounds: Sounds,
+    /// Font for scores and banners. raylib's default font is used when it is missing.
+    /// Shared with the overlays pushed over the level.
+    pub font: Option<Rc<Font>>,
+}
+
+impl Assets {
+    /// Loads skins and the font from the assets directory. Needs the window, and
+    /// the audio device for sounds.
+    pub fn load() -> Assets {
+        Assets {
+            skins: Skins::load(SKINS_DIR),
+            sounds: Sounds::load(),
+            font: load_font(FONT_PATH).map(Rc::new),
+        }
+    }
+
+    /// Flat shapes, the default font and no sound, without touching raylib.
+    pub fn none() -> Assets {
+        Assets {
+            skins: Skins::default(),
+            sounds: Sounds::silent(),
+            font: None,
+        }
+    }
+}
+
 pub struct Level0 {
     player: Player,
     enemy: Enemy,
     ball: Ball,
     bricks: Vec<Brick>,
     turn: Turn,
+    skins: Skins,
+    sounds: Sounds,
+    font: Option<Rc<Font>>,
+    /// Score text, rebuilt only when a point changes.
+    player_score: CachedText<i32>,
+    enemy_score: CachedText<i32>,
+    camera: CameraController,
+    particles: Particles,
+    /// Its passes are toggled from the pause menu.
+    post_processing: Option<Rc<RefCell<PostProcessing>>>,
+    /// Slows the match down as the ball nears a match-winning goal.
+    clock: GameClock,
     
-    pause: bool,
     before_start: bool,
     game_result: GameResult,
+
+    /// Which gamepads were plugged in last frame, to notice hot-plugging.
+    gamepads: [bool; MAX_GAMEPADS],
+    presses: Presses,
+}
+
+/// Presses read since the last update. A frame can run several updates or none,
+/// so each press is handled by the first update after it.
+#[derive(Default)]
+struct Presses {
+    /// [ENTER]
+    start_match: bool,
+    /// [P]
+    pause: bool,
+    /// [M]
+    toggle_mouse: bool,
+    /// [V]
+    toggle_shake: bool,
+    /// [SPACE]
+    serve: bool,
+    /// START, per gamepad.
+    gamepad_start: [bool; MAX_GAMEPADS],
+    /// An assigned gamepad was unplugged.
+    disconnected: bool,
 }
 
 impl Level0 {
-    pub fn new() -> Self {
+    pub fn new(assets: Assets, post_processing: Option<Rc<RefCell<PostProcessing>>>) -> Self {
         Self {
             player: Player::default(),
             enemy: Enemy::default(),
             ball: Ball::default(),
             bricks: Vec::new(),
             turn: Turn::Player,
+            skins: assets.skins,
+            sounds: assets.sounds,
+            font: assets.font,
+            player_score: CachedText::default(),
+            enemy_score: CachedText::default(),
+            camera: CameraController::new(
+                Vector2 { x: SCREEN_WIDTH as f32 / 2.0, y: SCREEN_HEIGHT as f32 / 2.0 },
+                SHAKE_INTENSITY,
+            ),
+            particles: Particles::new(MAX_PARTICLES),
+            post_processing,
+            clock: GameClock::default(),
             
-            pause: false,
             before_start: true,
             game_result: GameResult::Undetermined,
+
+            gamepads: [false; MAX_GAMEPADS],
+            presses: Presses::default(),
         }
     }
 }
 
+fn load_font(path: &str) -> Option<Font> {
+    if !Path::new(path).exists() {
+        return None;
+    }
+
+    let font = Font::load_ex(path, 64, &[])?;
+    font.set_filter(TextureFilter::Bilinear);
+
+    Some(font)
+}
+
 fn init_brick_line(bricks: &mut Vec<Brick>, y: f32, bricks_per_line: i32, colors: &[Color]) {
     let brick_size = Vector2 {
         x: (SCREEN_WIDTH / bricks_per_line) as f32,
@@ -64,7 +180,7 @@ fn init_brick_line(bricks: &mut Vec<Brick>, y: f32, bricks_per_line: i32, colors
                 x: (i as f32) * brick_size.x + brick_size.x / 2.0,
                 y,
             },
-            size: brick_size.clone(),
+            size: brick_size,
             color: colors[(i as usize) % colors.len()].clone(),
             active: true,
         });
@@ -73,12 +189,21 @@ fn init_brick_line(bricks: &mut Vec<Brick>, y: f32, bricks_per_line: i32, colors
 
 impl Scene for Level0 {
     fn init(&mut self) {
-        self.player = Player::default();
-        self.enemy = Enemy::default();
+        self.player = Player {
+            control: self.player.control,
+            ..Player::default()
+        };
+        self.enemy = Enemy {
+            gamepad: self.enemy.gamepad,
+            ..Enemy::default()
+        };
         self.ball = Ball::default();
         self.bricks = Vec::new();
         self.turn = Turn::Player;
-        self.pause = false;
+        self.game_result = GameResult::Undetermined;
+        self.camera.reset();
+        self.particles.clear();
+        self.clock.reset();
 
         // Top bricks
         init_brick_line(&mut self.bricks, BRICK_HEIGHT / 2.0, BRICKS_PER_LINE, &[GRAY, DARKGRAY]);
@@ -86,92 +211,219 @@ impl Scene for Level0 {
         init_brick_line(&mut self.bricks, SCREEN_HEIGHT as f32 - BRICK_HEIGHT / 2.0, BRICKS_PER_LINE, &[DARKGRAY, GRAY]);
     }
 
-    fn frame(&mut self, delta_time: f32) -> SceneResult {
-        self.update(delta_time);
-        self.draw();
+    fn handle_input(&mut self, rl: &RaylibHandle) {
+        self.presses.start_match |= rl.is_key_pressed(Key::Enter);
+        self.presses.pause |= rl.is_key_pressed(Key::P);
+        self.presses.toggle_mouse |= rl.is_key_pressed(Key::M);
+        self.presses.toggle_shake |= rl.is_key_pressed(Key::V);
+        self.presses.serve |= rl.is_key_pressed(Key::Space);
+        self.poll_gamepads(rl);
+    }
 
-        SceneResult::OnGoing
+    fn update(&mut self, rl: &RaylibHandle, delta_time: DeltaTime) -> SceneResult {
+        Level0::update(self, rl, delta_time)
+    }
+
+    fn draw(&mut self, d: &mut DrawHandle, alpha: f32) {
+        Level0::draw(self, d, alpha);
     }
 }
 
 impl Level0 {
-    fn update(&mut self, delta_time: f32) {
+    /// Paddles, ball and particles move in scaled time; the camera zooms and
+    /// shakes in real time, so it is not dragged out by slow motion. Nothing, not
+    /// even the camera, moves while game time stands still.
+    fn update(&mut self, rl: &RaylibHandle, delta_time: DeltaTime) -> SceneResult {
+        let delta_time = self.clock.advance(delta_time);
+
+        // Frozen, as when frame-paused with [F7]: presses wait for the update that
+        // steps the game, so they are not acted on between frames.
+        if delta_time.scaled <= 0.0 {
+            self.stand_still();
+            return SceneResult::OnGoing;
+        }
+
+        let presses = mem::take(&mut self.presses);
+        self.assign_gamepads(&presses.gamepad_start);
+
         if self.before_start {
-            if is_key_pressed(Key::Enter) {
+            if presses.start_match {
                 self.before_start = false;
                 self.init();
             }
         } else {
-            if is_key_pressed(Key::P) {
-                self.pause = !self.pause;
+            // A paddle whose gamepad is unplugged falls back to the keyboard or AI,
+            // so stop the match until it is back.
+            if presses.pause || presses.disconnected {
+                return SceneResult::Push(Box::new(PauseMenu::new(self.font.clone(), self.post_processing.clone())));
+            }
+
+            // A gamepad keeps the paddle until it is unplugged.
+            if presses.toggle_mouse {
+                self.player.control = match self.player.control {
+                    Control::Keyboard => Control::Mouse,
+                    Control::Mouse => Control::Keyboard,
+                    gamepad => gamepad,
+                };
             }
 
-            if self.pause {
-                return;
+            if presses.toggle_shake {
+                let intensity = if self.camera.shake_intensity() > 0.0 { 0.0 } else { SHAKE_INTENSITY };
+                self.camera.set_shake_intensity(intensity);
             }
 
-            move_player(&mut self.player, delta_time);
+            self.camera.update(delta_time.unscaled);
+            self.particles.update(delta_time.scaled);
+
+            move_player(rl, &mut self.player, delta_time.scaled);
+            move_enemy(rl, &mut self.enemy, &self.ball, delta_time.scaled);
 
             if !self.ball.active {
-                if is_key_pressed(Key::Space) {
+                if presses.serve {
                     self.ball.active = true;
                     init_ball(&mut self.ball, &self.turn);
                 } else {
-                    return;
+                    return SceneResult::OnGoing;
                 }
             }
 
-            move_ball(&mut self.ball, delta_time);
-            move_enemy(&mut self.enemy, &self.ball, delta_time);
+            move_ball(&mut self.ball, delta_time.scaled);
+
+            if on_collision_ball_walls(&mut self.ball, &mut self.player, &mut self.enemy, &mut self.turn, &mut self.game_result) {
+                self.sounds.play(Effect::Goal);
+                self.camera.shake(0.8);
 
-            on_collision_ball_walls(&mut self.ball, &mut self.player, &mut self.enemy, &mut self.turn, &mut self.game_result);
-            on_collision_ball_paddle(&mut self.ball, &self.player.collider());
-            on_collision_ball_paddle(&mut self.ball, &self.enemy.collider());
-            on_collision_ball_bricks(&mut self.ball, &mut self.bricks);
+                // Back into the field from whichever goal line was crossed.
+                let into_field = if self.ball.position.y < SCREEN_HEIGHT as f32 / 2.0 { 1.0 } else { -1.0 };
+                self.particles.emit(&GOAL_BURST, &self.ball.position, &Vector2 { x: 0.0, y: into_field });
+
+                let match_point = WINNING_SCORE - 1;
+                if matches!(self.game_result, GameResult::Undetermined) &&
+                    (self.player.point == match_point || self.enemy.point == match_point) {
+                    self.camera.zoom(0.15, 1.2);
+                }
+            }
+            if on_collision_ball_paddle(&mut self.ball, &self.player.collider()) {
+                self.sounds.play(Effect::Paddle);
+                self.particles.emit(&PADDLE_SPARKS, &self.ball.position, &self.ball.direction);
+            }
+            if on_collision_ball_paddle(&mut self.ball, &self.enemy.collider()) {
+                self.sounds.play(Effect::Paddle);
+                self.particles.emit(&PADDLE_SPARKS, &self.ball.position, &self.ball.direction);
+            }
+            if let Some(brick) = on_collision_ball_bricks(&mut self.ball, &mut self.bricks) {
+                self.sounds.play(Effect::Brick);
+                self.camera.shake(0.35);
+
+                let burst = Emitter {
+                    color: brick.color.clone(),
+                    ..BRICK_BURST
+                };
+                self.particles.emit(&burst, &brick.position, &Vector2::default());
+            }
+
+            // Renewed every update the ball stays close, so it lingers briefly after.
+            if self.ball.active && self.nears_winning_goal() {
+                self.clock.slow_motion(SLOW_MOTION_SCALE, 0.2);
+            }
+
+            if !matches!(self.game_result, GameResult::Undetermined) {
+                return SceneResult::Push(Box::new(MatchResult::new(self.game_result, self.font.clone())));
+            }
         }
+
+        SceneResult::OnGoing
     }
 
-    fn draw(&self) {
-        clear_background(&ELEGANT_BLACK);
-        
-        if self.before_start {
-            draw_text_center("Press [ENTER] to Play", 20, &MAROON);
+    /// Settles the paddles and ball where they are, as they are drawn between their
+    /// last two positions and would otherwise jitter while nothing moves them.
+    fn stand_still(&mut self) {
+        self.player.previous_position = self.player.position;
+        self.enemy.previous_position = self.enemy.position;
+        self.ball.previous_position = self.ball.position;
+    }
+
+    /// Whether the ball is heading into a goal, close to it, that would end the match.
+    fn nears_winning_goal(&self) -> bool {
+        let match_point = WINNING_SCORE - 1;
+
+        // The player scores at the top, the enemy at the bottom.
+        if self.ball.direction.y < 0.0 {
+            self.player.point == match_point && self.ball.position.y < SLOW_MOTION_DISTANCE
         } else {
-            draw_point(self.player.point, 20, 450);
-            draw_point(self.enemy.point, 400, 300);
+            self.enemy.point == match_point && self.ball.position.y > SCREEN_HEIGHT as f32 - SLOW_MOTION_DISTANCE
+        }
+    }
 
-            draw_player(&self.player);
-            draw_enemy(&self.enemy);
+    /// Notices assigned gamepads being unplugged and START pressed on the others.
+    fn poll_gamepads(&mut self, rl: &RaylibHandle) {
+        for gamepad in 0..MAX_GAMEPADS {
+            let id = gamepad as i32;
+            let available = rl.is_gamepad_available(id);
 
-            if self.ball.active {
-                draw_ball(&self.ball);
+            if self.gamepads[gamepad] && !available && self.is_assigned(id) {
+                self.presses.disconnected = true;
             }
 
-            for brick in &self.bricks {
-                if brick.active {
-                    draw_brick(brick);
-                }
+            self.gamepads[gamepad] = available;
+
+            if available && rl.is_gamepad_button_pressed(id, GamepadButton::MiddleRight) {
+                self.presses.gamepad_start[gamepad] = true;
             }
+        }
+    }
 
-            if !matches!(self.game_result, GameResult::Undetermined) {
-                draw_rectangle(0, 0, SCREEN_WIDTH, SCREEN_HEIGHT, &Color { r: 0, g: 0, b: 128, a: 200 });
-
-                match self.game_result {
-                    GameResult::PlayerWin => {
-                        draw_text_center("You Win!", 40, &MAROON)
-                    },
-                    GameResult::EnemyWin => {
-                        draw_text_center("Enemy Win!", 40, &MAROON)
-                    },
-                    _ => {},
-                }
+    /// Pressing START on a gamepad hands it to the player, then to the enemy paddle.
+    fn assign_gamepads(&mut self, start: &[bool; MAX_GAMEPADS]) {
+        for (gamepad, &pressed) in start.iter().enumerate() {
+            let id = gamepad as i32;
+
+            if !pressed || !self.gamepads[gamepad] || self.is_assigned(id) {
+                continue;
             }
 
-            if matches!(self.game_result, GameResult::Undetermined) && self.pause {
-                draw_text_center("PAUSE", 40, &MAROON);
+            if !matches!(self.player.control, Control::Gamepad(_)) {
+                self.player.control = Control::Gamepad(id);
+            } else if self.enemy.gamepad.is_none() {
+                self.enemy.gamepad = Some(id);
             }
         }
     }
+
+    fn is_assigned(&self, gamepad: i32) -> bool {
+        self.player.control == Control::Gamepad(gamepad) || self.enemy.gamepad == Some(gamepad)
+    }
+
+    /// `alpha` is how far the game is between its last update and the next one.
+    fn draw<R: Renderer>(&mut self, d: &mut R, alpha: f32) {
+        d.clear_background(&ELEGANT_BLACK);
+        
+        if self.before_start {
+            draw_text_center(d, self.font.as_deref(), "Press [ENTER] to Play", 20, &MAROON);
+        } else {
+            let player_score = self.player_score.get(self.player.point, |point| point.to_string());
+            draw_point(d, self.font.as_deref(), player_score, 20, 450);
+            let enemy_score = self.enemy_score.get(self.enemy.point, |point| point.to_string());
+            draw_point(d, self.font.as_deref(), enemy_score, 400, 300);
+
+            d.draw_mode_2d(self.camera.camera(), |d| {
+                draw_player(d, &self.player, alpha, self.skins.paddle.as_ref());
+                draw_enemy(d, &self.enemy, alpha, self.skins.paddle.as_ref());
+
+                if self.ball.active {
+                    draw_ball(d, &self.ball, alpha, self.skins.ball.as_ref());
+                }
+
+                for brick in &self.bricks {
+                    if brick.active {
+                        draw_brick(d, brick, self.skins.brick.as_ref());
+                    }
+                }
+
+                self.particles.draw(d);
+            });
+        }
+    }
 }
 
 fn on_collision_ball_walls(
@@ -180,7 +432,9 @@ fn on_collision_ball_walls(
     enemy: &mut Enemy, 
     turn: &mut Turn,
     game_result: &mut GameResult,
-) {
+) -> bool {
+    let mut goal = false;
+
     if ball.position.x + ball.radius >= SCREEN_WIDTH as f32 || 
         ball.position.x - ball.radius <= 0.0 {
         ball.direction.x *= -1.0;
@@ -189,8 +443,9 @@ fn on_collision_ball_walls(
         ball.active = false;
         player.point += 1;
         *turn = Turn::Player;
+        goal = true;
 
-        if player.point >= 7 {
+        if player.point >= WINNING_SCORE {
             *game_result = GameResult::PlayerWin;
         }
     }
@@ -198,41 +453,133 @@ fn on_collision_ball_walls(
         ball.active = false;
         enemy.point += 1;
         *turn = Turn::Enemy;
+        goal = true;
 
-        if enemy.point >= 7 {
+        if enemy.point >= WINNING_SCORE {
             *game_result = GameResult::EnemyWin;
         }
     }
+
+    goal
 }
 
-fn on_collision_ball_paddle(ball: &mut Ball, paddle: &Rectangle) {
+fn on_collision_ball_paddle(ball: &mut Ball, paddle: &Rectangle) -> bool {
     if collide_ball(ball, paddle) {
         ball.direction.y *= -1.0;
         ball.direction.x = (ball.position.x - paddle.x) / (paddle.width / 2.0);
         ball.direction.x /= 3.0;
 
         ball.direction.normalize();
+
+        return true;
     }
+
+    false
 }
 
-fn on_collision_ball_bricks(ball: &mut Ball, bricks: &mut Vec<Brick>) {
+/// Returns the brick that was broken, if any.
+fn on_collision_ball_bricks<'a>(ball: &mut Ball, bricks: &'a mut [Brick]) -> Option<&'a Brick> {
     for brick in bricks {
         if brick.active && collide_ball(ball, &brick.collider()) {
             brick.active = false;
-            ball.direction.y *= -1.0;
+            ball.direction = ball.direction.reflect(&Vector2 { x: 0.0, y: 1.0 });
 
-            break;
+            return Some(brick);
         }
     }
+
+    None
 }
 
-fn draw_text_center(text: &str, font_size: i32, color: &Color) {
-    let x = SCREEN_WIDTH / 2 - measure_text(&text, font_size) / 2;
-    let y = SCREEN_HEIGHT / 2;
+/// Draws `text` in the middle of the canvas, with `font` when there is one.
+pub fn draw_text_center<R: Renderer>(d: &mut R, font: Option<&Font>, text: &str, font_size: i32, color: &Color) {
+    let font = match font {
+        Some(font) => font,
+        None => {
+            let x = SCREEN_WIDTH / 2 - d.measure_text(text, font_size) / 2;
+            let y = SCREEN_HEIGHT / 2;
+
+            return d.draw_text(text, x, y, font_size, color);
+        },
+    };
+
+    // Same spacing draw_text uses for the default font.
+    let size = font_size as f32;
+    let spacing = size / 10.0;
+    let measured = d.measure_text_ex(font, text, size, spacing);
+    let position = Vector2 {
+        x: (SCREEN_WIDTH as f32 - measured.x) / 2.0,
+        y: (SCREEN_HEIGHT / 2) as f32,
+    };
 
-    draw_text(text, x, y, font_size, color);
+    d.draw_text_ex(font, text, &position, size, spacing, color);
 }
 
-fn draw_point(point: i32, x: i32, y: i32) {
-    draw_text(&format!("{}", point), x, y, 40, &GRAY);
+fn draw_point<R: Renderer>(d: &mut R, font: Option<&Font>, text: &CStr, x: i32, y: i32) {
+    match font {
+        Some(font) => {
+            let position = Vector2 { x: x as f32, y: y as f32 };
+
+            d.draw_text_ex(font, text, &position, 40.0, 4.0, &GRAY);
+        },
+        None => d.draw_text(text, x, y, 40, &GRAY),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Level0, Assets};
+    use crate::consts::{SCREEN_HEIGHT, SCREEN_WIDTH};
+    use crate::levels::overlays::PauseMenu;
+    use crate::raylib::{Vector2, MAROON};
+    use crate::renderer::{DrawCommand, Recorder, Renderer};
+
+    #[test]
+    fn paused_match_draws_pause_over_the_level() {
+        let mut level = Level0::new(Assets::none(), None);
+        level.before_start = false;
+        let mut pause_menu = PauseMenu::new(level.font.clone(), None);
+
+        let mut d = Recorder::new();
+        level.draw(&mut d, 1.0);
+        pause_menu.draw(&mut d);
+
+        // The match stays on screen under the menu: scores, then paddles through the camera.
+        assert_eq!(d.texts().collect::<Vec<_>>(), ["0", "0", "PAUSE"]);
+        assert!(d.commands().contains(&DrawCommand::EndMode2D));
+
+        // No post-processing, so no pass toggles.
+        assert!(!d.commands().iter().any(|command| matches!(command, DrawCommand::Button { .. })));
+
+        let x = SCREEN_WIDTH / 2 - d.measure_text("PAUSE", 40) / 2;
+        assert_eq!(d.commands().last(), Some(&DrawCommand::Text {
+            text: "PAUSE".to_string(),
+            position: Vector2 { x: x as f32, y: (SCREEN_HEIGHT / 2) as f32 },
+            font_size: 40.0,
+            color: MAROON,
+        }));
+    }
+
+    #[test]
+    fn frozen_match_draws_the_same_at_any_alpha() {
+        let mut level = Level0::new(Assets::none(), None);
+        level.before_start = false;
+        level.ball.active = true;
+        level.ball.position = Vector2 { x: 100.0, y: 200.0 };
+        level.ball.previous_position = Vector2 { x: 90.0, y: 190.0 };
+        level.player.previous_position = level.player.position - Vector2 { x: 10.0, y: 0.0 };
+        level.enemy.previous_position = level.enemy.position + Vector2 { x: 10.0, y: 0.0 };
+
+        let draw = |level: &mut Level0, alpha| {
+            let mut d = Recorder::new();
+            level.draw(&mut d, alpha);
+            d.commands().to_vec()
+        };
+
+        assert_ne!(draw(&mut level, 0.25), draw(&mut level, 0.75));
+
+        level.stand_still();
+
+        assert_eq!(draw(&mut level, 0.25), draw(&mut level, 0.75));
+    }
 }
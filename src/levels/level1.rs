@@ -1,9 +1,8 @@
 use crate::raylib::{
-    Scene, SceneResult, Rectangle,
-    draw_text,
-    gui_button,
+    Rectangle, DrawHandle,
     RAYWHITE,
 };
+use crate::scene::{FrameScene, SceneResult};
 
 pub struct Level1 {
     // player: Player,
@@ -23,17 +22,25 @@ impl Level1 {
     }
 }
 
-impl Scene for Level1 {
-    fn frame(&mut self, delta_time: f32) -> SceneResult {
-        draw_text("Hello, level 1!", 12, 12, 20, &RAYWHITE);
+impl FrameScene for Level1 {
+    fn frame(&mut self, d: &mut DrawHandle, delta_time: f32) -> SceneResult {
+        self.draw(d);
 
-        gui_button(&Rectangle {
+        let go_to_level2 = d.gui_button(&Rectangle {
             x: 12.0,
             y: 60.0,
             width: 100.0,
             height: 40.0,
         }, "Go to level 2!");
 
+        if go_to_level2 {
+            return SceneResult::GoToNamed("level2".to_string());
+        }
+
         SceneResult::OnGoing
     }
+
+    fn draw(&mut self, d: &mut DrawHandle) {
+        d.draw_text("Hello, level 1!", 12, 12, 20, &RAYWHITE);
+    }
 }
\ No newline at end of file
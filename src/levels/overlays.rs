@@ -0,0 +1,148 @@
+use crate::raylib::{
+    Color, Rectangle,
+    MAROON,
+    Key,
+    DrawHandle, RaylibHandle,
+    Font,
+};
+use crate::consts::{
+    SCREEN_HEIGHT, SCREEN_WIDTH,
+};
+use crate::clock::DeltaTime;
+use crate::effects::PostProcessing;
+use crate::scene::{Scene, SceneResult};
+use crate::levels::level0::{GameResult, draw_text_center};
+use crate::renderer::Renderer;
+
+use std::cell::RefCell;
+use std::mem;
+use std::rc::Rc;
+
+/// Pushed over a level while it is paused. Lists a toggle per post-processing
+/// pass; [P] resumes.
+pub struct PauseMenu {
+    font: Option<Rc<Font>>,
+    post_processing: Option<Rc<RefCell<PostProcessing>>>,
+    /// [P] was pressed since the last update.
+    resume: bool,
+    /// Passes whose toggle was clicked since the last update. Buttons report clicks
+    /// as they are drawn, and the passes change in `update`.
+    toggled: Vec<usize>,
+}
+
+impl PauseMenu {
+    pub fn new(font: Option<Rc<Font>>, post_processing: Option<Rc<RefCell<PostProcessing>>>) -> Self {
+        Self {
+            font,
+            post_processing,
+            resume: false,
+            toggled: Vec::new(),
+        }
+    }
+
+    pub fn draw<R: Renderer>(&mut self, d: &mut R) {
+        draw_text_center(d, self.font.as_deref(), "PAUSE", 40, &MAROON);
+        self.draw_options(d);
+    }
+
+    fn draw_options<R: Renderer>(&mut self, d: &mut R) {
+        let post_processing = match &self.post_processing {
+            Some(post_processing) => post_processing,
+            None => return,
+        };
+
+        let post_processing = post_processing.borrow();
+
+        for (i, pass) in post_processing.passes().iter().enumerate() {
+            let bounds = Rectangle {
+                x: (SCREEN_WIDTH as f32 - OPTION_WIDTH) / 2.0,
+                y: SCREEN_HEIGHT as f32 / 2.0 + 50.0 + i as f32 * (OPTION_HEIGHT + 10.0),
+                width: OPTION_WIDTH,
+                height: OPTION_HEIGHT,
+            };
+            let label = format!("{}: {}", pass.name(), if pass.enabled() { "On" } else { "Off" });
+
+            if d.gui_button(&bounds, &label) {
+                self.toggled.push(i);
+            }
+        }
+    }
+}
+
+impl Scene for PauseMenu {
+    fn handle_input(&mut self, rl: &RaylibHandle) {
+        self.resume |= rl.is_key_pressed(Key::P);
+    }
+
+    fn update(&mut self, _rl: &RaylibHandle, _delta_time: DeltaTime) -> SceneResult {
+        if let Some(post_processing) = &self.post_processing {
+            let mut post_processing = post_processing.borrow_mut();
+
+            for i in self.toggled.drain(..) {
+                if let Some(pass) = post_processing.passes_mut().get_mut(i) {
+                    pass.set_enabled(!pass.enabled());
+                }
+            }
+        }
+
+        if mem::take(&mut self.resume) {
+            return SceneResult::Pop;
+        }
+
+        SceneResult::OnGoing
+    }
+
+    fn draw(&mut self, d: &mut DrawHandle, _alpha: f32) {
+        PauseMenu::draw(self, d);
+    }
+}
+
+/// Pushed over a level once the match is decided. [ENTER] starts a new one.
+pub struct MatchResult {
+    game_result: GameResult,
+    font: Option<Rc<Font>>,
+    /// [ENTER] was pressed since the last update.
+    restart: bool,
+}
+
+impl MatchResult {
+    pub fn new(game_result: GameResult, font: Option<Rc<Font>>) -> Self {
+        Self {
+            game_result,
+            font,
+            restart: false,
+        }
+    }
+}
+
+impl Scene for MatchResult {
+    fn handle_input(&mut self, rl: &RaylibHandle) {
+        self.restart |= rl.is_key_pressed(Key::Enter);
+    }
+
+    fn update(&mut self, _rl: &RaylibHandle, _delta_time: DeltaTime) -> SceneResult {
+        if mem::take(&mut self.restart) {
+            return SceneResult::Restart;
+        }
+
+        SceneResult::OnGoing
+    }
+
+    fn draw(&mut self, d: &mut DrawHandle, _alpha: f32) {
+        let screen = Rectangle { x: 0.0, y: 0.0, width: SCREEN_WIDTH as f32, height: SCREEN_HEIGHT as f32 };
+        d.draw_rectangle_rec(&screen, &Color { r: 0, g: 0, b: 128, a: 200 });
+
+        match self.game_result {
+            GameResult::PlayerWin => {
+                draw_text_center(d, self.font.as_deref(), "You Win!", 40, &MAROON)
+            },
+            GameResult::EnemyWin => {
+                draw_text_center(d, self.font.as_deref(), "Enemy Win!", 40, &MAROON)
+            },
+            _ => {},
+        }
+    }
+}
+
+const OPTION_WIDTH: f32 = 220.0;
+const OPTION_HEIGHT: f32 = 36.0;
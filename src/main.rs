@@ -1,24 +1,47 @@
 use crate::raylib::{
-    init_window, set_target_fps, window_should_close, close_window,
-    begin_drawing, end_drawing, clear_background, draw_text,
+    init_window, set_config_flags,
+    AudioDevice, ConfigFlags, DrawHandle, RaylibHandle, RaylibWindow,
     Vector2, Color,
-    Scene, SceneResult, SceneManager,
-    RAYWHITE, BLACK,
+    BLACK,
 };
+use crate::canvas::Canvas;
+use crate::capture::Capture;
+use crate::clock::DeltaTime;
+use crate::scene::{Scene, SceneResult, SceneManager, FrameAdapter};
+use crate::transition::{Transition, TransitionEffect, Easing};
 
 use crate::consts::{
     SCREEN_WIDTH, SCREEN_HEIGHT,
+    CAPTURE_DIR,
+    SCENE_ENV,
 };
 
-use crate::levels::level0::Level0;
+use crate::effects::load_post_processing;
+use crate::levels::level0::{Level0, Assets};
 use crate::levels::level1::Level1;
 
+use std::cell::RefCell;
+use std::env;
+use std::rc::Rc;
+
 pub mod raylib;
+pub mod clock;
+pub mod scene;
+pub mod transition;
+pub mod canvas;
+pub mod capture;
+pub mod gif;
 pub mod consts;
 pub mod objects;
+pub mod sounds;
+pub mod camera;
+pub mod particles;
+pub mod effects;
+pub mod renderer;
 pub mod levels {
     pub mod level0;
     pub mod level1;
+    pub mod overlays;
 }
 
 #[derive(Default)]
@@ -85,26 +108,91 @@ struct Level2 {
 }
 
 impl Scene for Level2 {
-    fn frame(&mut self, delta_time: f32) -> SceneResult {
-        draw_text("Hello, level 2!", 12, 12, 20, &BLACK);
-
+    fn update(&mut self, _rl: &RaylibHandle, delta_time: DeltaTime) -> SceneResult {
         SceneResult::OnGoing
     }
+
+    fn draw(&mut self, d: &mut DrawHandle, _alpha: f32) {
+        d.draw_text("Hello, level 2!", 12, 12, 20, &BLACK);
+    }
+}
+
+/// Scene to start in, from `--scene <name>`, `--scene=<name>` or the `PONG_SCENE`
+/// environment variable, in that order.
+fn starting_scene() -> Option<String> {
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        if arg == "--scene" {
+            return args.next();
+        }
+
+        if let Some(name) = arg.strip_prefix("--scene=") {
+            return Some(name.to_string());
+        }
+    }
+
+    env::var(SCENE_ENV).ok().filter(|name| !name.is_empty())
 }
 
 fn main() {
-    init_window(SCREEN_WIDTH, SCREEN_HEIGHT, "Yet Another Pong");
+    set_config_flags(ConfigFlags::WindowResizable as u32);
+
+    let mut rl = init_window(SCREEN_WIDTH, SCREEN_HEIGHT, "Yet Another Pong");
 
-    set_target_fps(60);
+    rl.set_target_fps(60);
+    rl.set_window_min_size(SCREEN_WIDTH / 2, SCREEN_HEIGHT / 2);
+
+    // Stays `None` without a sound card, in which case the game runs silently.
+    let audio = AudioDevice::init();
+
+    // The game is laid out for a SCREEN_WIDTH x SCREEN_HEIGHT canvas, letterboxed into the window.
+    // Without one, scenes draw straight to the window, which then must keep its size,
+    // and there is no post-processing.
+    let mut canvas = Canvas::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+    let mut post_processing = None;
+
+    match &mut canvas {
+        Some(canvas) => {
+            post_processing = load_post_processing(SCREEN_WIDTH, SCREEN_HEIGHT).map(|post_processing| Rc::new(RefCell::new(post_processing)));
+
+            if let Some(post_processing) = &post_processing {
+                canvas.set_post_processing(post_processing.clone());
+            }
+        },
+        None => rl.clear_window_state(ConfigFlags::WindowResizable as u32),
+    }
 
     let mut scene_manager = SceneManager::new();
-    scene_manager.add(Box::new(Level0::new()));
-    scene_manager.add(Box::new(Level1::new()));
-    scene_manager.add(Box::new(Level2::default()));
+    scene_manager.add("level0", Box::new(Level0::new(Assets::load(), post_processing)));
+    scene_manager.add("level1", Box::new(FrameAdapter::new(Level1::new())));
+    scene_manager.add("level2", Box::new(Level2::default()));
 
     scene_manager.set(0);
 
-    scene_manager.run();
+    if let Some(name) = starting_scene() {
+        if !scene_manager.set_named(&name) {
+            let names: Vec<&str> = scene_manager.names().collect();
+            eprintln!("No scene named \"{}\", starting from the first one. Scenes: {}", name, names.join(", "));
+        }
+    }
+
+    if let Some(canvas) = canvas {
+        scene_manager.set_canvas(canvas);
+    }
+
+    scene_manager.set_capture(Capture::new(CAPTURE_DIR));
+    scene_manager.set_frame_stepping(cfg!(debug_assertions));
+    scene_manager.set_transition(Transition {
+        effect: TransitionEffect::Fade(BLACK),
+        duration: 0.6,
+        easing: Easing::EaseInOut,
+    });
+
+    scene_manager.run(&mut rl);
 
-    close_window();
+    // Scenes own textures and sounds, so they go first, then the audio device, then the window.
+    drop(scene_manager);
+    drop(audio);
+    drop(rl);
 }
\ No newline at end of file
@@ -2,36 +2,99 @@ use crate::consts::{
     SCREEN_WIDTH, SCREEN_HEIGHT,
     PLAYER_SPEED,
     PADDLE_GRAY, BALL_SPEED,
+    GAMEPAD_DEADZONE,
+    BALL_TRAIL_LENGTH,
 };
 
 use crate::raylib::{
     Color, Vector2, Rectangle, Circle, 
     check_collision_circle_rec,
-    draw_rectangle,
-    draw_circle_v,
-    is_key_down, Key,
-    MAROON,
+    RaylibHandle, RaylibWindow,
+    Key, GamepadAxis, GamepadButton,
+    Texture,
+    MAROON, WHITE,
 };
 
+use crate::renderer::Renderer;
+
+use std::collections::VecDeque;
+use std::path::Path;
+
+/// Optional sprites drawn in place of the flat shapes.
+#[derive(Default)]
+pub struct Skins {
+    pub paddle: Option<Texture>,
+    pub ball: Option<Texture>,
+    pub brick: Option<Texture>,
+}
+
+impl Skins {
+    /// Loads `paddle.png`, `ball.png` and `brick.png` from `directory`, skipping missing files.
+    pub fn load(directory: &str) -> Skins {
+        let load = |name: &str| {
+            let path = Path::new(directory).join(name);
+
+            if path.exists() {
+                Texture::load(path.to_str()?)
+            } else {
+                None
+            }
+        };
+
+        Skins {
+            paddle: load("paddle.png"),
+            ball: load("ball.png"),
+            brick: load("brick.png"),
+        }
+    }
+}
+
+fn draw_skin<R: Renderer>(d: &mut R, skin: &Texture, position: &Vector2, size: &Vector2, tint: &Color) {
+    d.draw_texture_pro(
+        skin,
+        &skin.bounds(),
+        &Rectangle::from(position, size),
+        &Vector2::default(),
+        0.0,
+        tint,
+    );
+}
+
 pub enum Turn {
     Player,
     Enemy,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Keyboard,
+    /// The paddle chases the mouse x, no faster than its speed.
+    Mouse,
+    /// Left stick or d-pad of the given gamepad. Falls back to the keyboard while it is unplugged.
+    Gamepad(i32),
+}
+
 pub struct Player {
     pub position: Vector2,
+    /// Position before the last move, drawn blended towards `position`.
+    pub previous_position: Vector2,
     pub size: Vector2,
     pub point: i32,
     pub speed: f32,
+    pub control: Control,
 }
 
 impl Default for Player {
     fn default() -> Self {
+        let position = Vector2 { x: (SCREEN_WIDTH / 2) as f32 , y: (SCREEN_HEIGHT * 7 / 8) as f32 };
+
         Player {
-            position: Vector2 { x: (SCREEN_WIDTH / 2) as f32 , y: (SCREEN_HEIGHT * 7 / 8) as f32 },
+            position,
+            previous_position: position,
             size: Vector2 { x: (SCREEN_WIDTH / 10) as f32, y: 20.0 },
             point: 0,
             speed: PLAYER_SPEED,
+            control: Control::Keyboard,
         }
     }
 }
@@ -42,49 +105,111 @@ impl Player {
     }
 }
 
-pub fn move_player(player: &mut Player, delta_time: f32) {
-    let mut direction = Vector2 { x: 0.0, y: 0.0 };
+pub fn move_player(rl: &RaylibHandle, player: &mut Player, delta_time: f32) {
+    player.previous_position = player.position;
+
+    match player.control {
+        Control::Keyboard => {
+            player.position.x += keyboard_direction(rl) * player.speed * delta_time;
+        },
+        Control::Mouse => {
+            let max_step = player.speed * delta_time;
+            let offset = rl.get_mouse_position().x - player.position.x;
+
+            player.position.x += offset.clamp(-max_step, max_step);
+        },
+        Control::Gamepad(gamepad) => {
+            let direction = gamepad_direction(rl, gamepad).unwrap_or_else(|| keyboard_direction(rl));
+
+            player.position.x += direction * player.speed * delta_time;
+        },
+    }
+
+    clamp_paddle(&mut player.position, &player.size);
+}
+
+fn keyboard_direction(rl: &RaylibHandle) -> f32 {
+    let mut direction = 0.0;
 
-    if is_key_down(Key::Left) {
-        direction.x -= 1.0;
+    if rl.is_key_down(Key::Left) {
+        direction -= 1.0;
     }
-    
-    if is_key_down(Key::Right) {
-        direction.x += 1.0;
+
+    if rl.is_key_down(Key::Right) {
+        direction += 1.0;
     }
 
-    direction.normalize();
+    direction
+}
+
+/// Horizontal input of a gamepad in `-1.0..=1.0`, or `None` while it is unplugged.
+pub fn gamepad_direction(rl: &RaylibHandle, gamepad: i32) -> Option<f32> {
+    if !rl.is_gamepad_available(gamepad) {
+        return None;
+    }
 
-    player.position += direction * player.speed * delta_time;
+    let mut direction = apply_deadzone(rl.get_gamepad_axis_movement(gamepad, GamepadAxis::LeftX), GAMEPAD_DEADZONE);
 
-    if player.position.x - player.size.x / 2.0 <= 0.0 {
-        player.position.x = player.size.x / 2.0;
+    if rl.is_gamepad_button_down(gamepad, GamepadButton::LeftFaceLeft) {
+        direction -= 1.0;
     }
 
-    if player.position.x + player.size.x / 2.0 >= SCREEN_WIDTH as f32 {
-        player.position.x = SCREEN_WIDTH as f32 - player.size.x / 2.0;
+    if rl.is_gamepad_button_down(gamepad, GamepadButton::LeftFaceRight) {
+        direction += 1.0;
     }
+
+    Some(direction.clamp(-1.0, 1.0))
 }
 
-pub fn draw_player(player: &Player) {
-    draw_rectangle(
-        (player.position.x - player.size.x / 2.0) as i32, 
-        (player.position.y - player.size.y / 2.0) as i32, 
-        player.size.x as i32, 
-        player.size.y as i32, 
-        &PADDLE_GRAY,
-    );
+/// Zeroes stick drift inside the deadzone and rescales the rest back to the full range.
+fn apply_deadzone(value: f32, deadzone: f32) -> f32 {
+    if value.abs() <= deadzone {
+        return 0.0;
+    }
+
+    (value.abs() - deadzone) / (1.0 - deadzone) * value.signum()
+}
+
+fn clamp_paddle(position: &mut Vector2, size: &Vector2) {
+    if position.x - size.x / 2.0 <= 0.0 {
+        position.x = size.x / 2.0;
+    }
+
+    if position.x + size.x / 2.0 >= SCREEN_WIDTH as f32 {
+        position.x = SCREEN_WIDTH as f32 - size.x / 2.0;
+    }
+}
+
+/// `alpha` blends from the previous position (0.0) to the current one (1.0).
+pub fn draw_player<R: Renderer>(d: &mut R, player: &Player, alpha: f32, skin: Option<&Texture>) {
+    let position = player.previous_position.lerp(&player.position, alpha);
+
+    if let Some(skin) = skin {
+        return draw_skin(d, skin, &position, &player.size, &WHITE);
+    }
+
+    d.draw_rectangle_rec(&Rectangle::from(&position, &player.size), &PADDLE_GRAY);
 }
 
 pub struct Enemy {
     pub position: Vector2,
+    /// Position before the last move, drawn blended towards `position`.
+    pub previous_position: Vector2,
     pub size: Vector2,
     pub point: i32,
     pub speed: f32,
+    /// A second player's gamepad. The AI takes over while it is `None` or unplugged.
+    pub gamepad: Option<i32>,
 }
 
-pub fn move_enemy(enemy: &mut Enemy, ball: &Ball, delta_time: f32) {
-    if ball.active {
+pub fn move_enemy(rl: &RaylibHandle, enemy: &mut Enemy, ball: &Ball, delta_time: f32) {
+    enemy.previous_position = enemy.position;
+
+    if let Some(direction) = enemy.gamepad.and_then(|gamepad| gamepad_direction(rl, gamepad)) {
+        enemy.position.x += direction * enemy.speed * delta_time;
+
+        clamp_paddle(&mut enemy.position, &enemy.size);
+    } else if ball.active {
         if ball.position.x < enemy.position.x {
             enemy.position.x -= enemy.speed * delta_time;
         }
@@ -94,23 +219,28 @@ pub fn move_enemy(enemy: &mut Enemy, ball: &Ball, delta_time: f32) {
     }
 }
 
-pub fn draw_enemy(enemy: &Enemy) {
-    draw_rectangle(
-        (enemy.position.x - enemy.size.x / 2.0) as i32, 
-        (enemy.position.y - enemy.size.y / 2.0) as i32, 
-        enemy.size.x as i32, 
-        enemy.size.y as i32, 
-        &PADDLE_GRAY,
-    );
+/// `alpha` blends from the previous position (0.0) to the current one (1.0).
+pub fn draw_enemy<R: Renderer>(d: &mut R, enemy: &Enemy, alpha: f32, skin: Option<&Texture>) {
+    let position = enemy.previous_position.lerp(&enemy.position, alpha);
+
+    if let Some(skin) = skin {
+        return draw_skin(d, skin, &position, &enemy.size, &WHITE);
+    }
+
+    d.draw_rectangle_rec(&Rectangle::from(&position, &enemy.size), &PADDLE_GRAY);
 }
 
 impl Default for Enemy {
     fn default() -> Self {
+        let position = Vector2 { x: (SCREEN_WIDTH / 2) as f32, y: (SCREEN_HEIGHT / 8) as f32 };
+
         Enemy {
-            position: Vector2 { x: (SCREEN_WIDTH / 2) as f32, y: (SCREEN_HEIGHT / 8) as f32 },
+            position,
+            previous_position: position,
             size: Vector2 { x: (SCREEN_WIDTH / 10) as f32, y: 20.0 },
             point: 0,
             speed: PLAYER_SPEED,
+            gamepad: None,
         }
     }
 }
@@ -123,10 +253,13 @@ impl Enemy {
 
 pub struct Ball {
     pub position: Vector2,
+    /// Position before the last move, drawn blended towards `position`.
+    pub previous_position: Vector2,
     pub direction: Vector2,
     pub speed: f32,
     pub radius: f32,
     pub active: bool,
+    pub trail: Trail,
 }
 
 impl Ball {
@@ -139,10 +272,12 @@ impl Default for Ball {
     fn default() -> Self {
         Ball {
             position: Vector2 { x: 0.0, y: 0.0 },
+            previous_position: Vector2 { x: 0.0, y: 0.0 },
             direction: Vector2 { x: 0.0, y: 0.0 },
             speed: BALL_SPEED,
             radius: 7.0,
             active: false,
+            trail: Trail::new(BALL_TRAIL_LENGTH),
         }
     }
 }
@@ -152,11 +287,15 @@ pub fn init_ball(ball: &mut Ball, turn: &Turn) {
         Turn::Player => Vector2 { x: (SCREEN_WIDTH / 2) as f32, y: (SCREEN_HEIGHT * 7 / 8 - 30) as f32 },
         Turn::Enemy => Vector2 { x: (SCREEN_WIDTH / 2) as f32, y: (SCREEN_HEIGHT / 8 + 30) as f32 },
     };
+    // Serving is a jump, not a move to blend.
+    ball.previous_position = ball.position;
 
     ball.direction = match turn {
         Turn::Player => Vector2 { x: 0.0, y: -1.0 },
         Turn::Enemy => Vector2 { x: 0.0, y: 1.0 },
     };
+
+    ball.trail.clear();
 }
 
 pub fn collide_ball(ball: &mut Ball, rectangle: &Rectangle) -> bool {
@@ -167,13 +306,87 @@ pub fn collide_ball(ball: &mut Ball, rectangle: &Rectangle) -> bool {
 }
 
 pub fn move_ball(ball: &mut Ball, delta_time: f32) {
+    ball.previous_position = ball.position;
+
     if ball.active {
-        ball.position += ball.direction.clone() * ball.speed * delta_time;
+        ball.trail.push(ball.position);
+        ball.position += ball.direction * ball.speed * delta_time;
     }
 }
 
-pub fn draw_ball(ball: &Ball) {
-    draw_circle_v(&ball.position, ball.radius, &MAROON);
+/// `alpha` blends from the previous position (0.0) to the current one (1.0).
+pub fn draw_ball<R: Renderer>(d: &mut R, ball: &Ball, alpha: f32, skin: Option<&Texture>) {
+    let position = ball.previous_position.lerp(&ball.position, alpha);
+
+    draw_trail(d, &ball.trail, ball.radius);
+
+    if let Some(skin) = skin {
+        let size = Vector2 { x: ball.radius * 2.0, y: ball.radius * 2.0 };
+
+        return draw_skin(d, skin, &position, &size, &WHITE);
+    }
+
+    d.draw_circle_v(&position, ball.radius, &MAROON);
+}
+
+/// The last few positions of the ball, oldest first, drawn behind it as afterimages.
+pub struct Trail {
+    positions: VecDeque<Vector2>,
+    length: usize,
+}
+
+impl Trail {
+    /// A `length` of 0 disables the trail.
+    pub fn new(length: usize) -> Trail {
+        Trail {
+            positions: VecDeque::with_capacity(length),
+            length,
+        }
+    }
+
+    /// Records a position, forgetting the oldest one once the trail is full.
+    pub fn push(&mut self, position: Vector2) {
+        if self.length == 0 {
+            return;
+        }
+
+        if self.positions.len() == self.length {
+            self.positions.pop_front();
+        }
+
+        self.positions.push_back(position);
+    }
+
+    pub fn clear(&mut self) {
+        self.positions.clear();
+    }
+
+    pub fn set_length(&mut self, length: usize) {
+        self.length = length;
+
+        while self.positions.len() > length {
+            self.positions.pop_front();
+        }
+    }
+}
+
+/// Opacity of the afterimage right behind the ball.
+const TRAIL_ALPHA: f32 = 160.0;
+
+/// Older afterimages are smaller and more transparent.
+fn draw_trail<R: Renderer>(d: &mut R, trail: &Trail, radius: f32) {
+    let steps = trail.length as f32 + 1.0;
+    let newest = trail.length - trail.positions.len();
+
+    for (i, position) in trail.positions.iter().enumerate() {
+        let strength = (newest + i + 1) as f32 / steps;
+        let color = Color {
+            a: (TRAIL_ALPHA * strength) as u8,
+            ..MAROON
+        };
+
+        d.draw_circle_v(position, radius * strength, &color);
+    }
 }
 
 pub struct Brick {
@@ -189,12 +402,10 @@ impl Brick {
     }
 }
 
-pub fn draw_brick(brick: &Brick) {
-    draw_rectangle(
-        (brick.position.x - brick.size.x / 2.0) as i32, 
-        (brick.position.y - brick.size.y / 2.0) as i32, 
-        brick.size.x as i32, 
-        brick.size.y as i32, 
-        &brick.color,
-    );
+pub fn draw_brick<R: Renderer>(d: &mut R, brick: &Brick, skin: Option<&Texture>) {
+    if let Some(skin) = skin {
+        return draw_skin(d, skin, &brick.position, &brick.size, &brick.color);
+    }
+
+    d.draw_rectangle_rec(&brick.collider(), &brick.color);
 }
@@ -0,0 +1,322 @@
+use std::f32::consts::PI;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::raylib::{
+    Color, Vector2,
+};
+use crate::renderer::Renderer;
+
+/// How a burst of particles looks and moves. Ranges are picked from at random per particle.
+pub struct Emitter {
+    pub count: usize,
+    /// Pixels per second.
+    pub speed: (f32, f32),
+    /// Radians either side of the emit direction. `PI` sprays in every direction.
+    pub spread: f32,
+    /// Seconds.
+    pub lifetime: (f32, f32),
+    /// Pixels per second squared.
+    pub gravity: Vector2,
+    /// Diameter at birth and at death, in pixels.
+    pub size: (f32, f32),
+    pub color: Color,
+}
+
+pub const BRICK_BURST: Emitter = Emitter {
+    count: 24,
+    speed: (60.0, 220.0),
+    spread: PI,
+    lifetime: (0.4, 0.9),
+    gravity: Vector2 { x: 0.0, y: 400.0 },
+    size: (6.0, 1.0),
+    color: Color { r: 200, g: 200, b: 200, a: 255 },
+};
+
+pub const PADDLE_SPARKS: Emitter = Emitter {
+    count: 10,
+    speed: (120.0, 300.0),
+    spread: PI / 5.0,
+    lifetime: (0.15, 0.35),
+    gravity: Vector2 { x: 0.0, y: 0.0 },
+    size: (4.0, 0.0),
+    color: Color { r: 255, g: 220, b: 120, a: 255 },
+};
+
+pub const GOAL_BURST: Emitter = Emitter {
+    count: 60,
+    speed: (100.0, 400.0),
+    spread: PI / 2.0,
+    lifetime: (0.6, 1.2),
+    gravity: Vector2 { x: 0.0, y: 0.0 },
+    size: (8.0, 2.0),
+    color: Color { r: 190, g: 33, b: 55, a: 255 },
+};
+
+pub struct Particle {
+    pub position: Vector2,
+    pub velocity: Vector2,
+    pub gravity: Vector2,
+    pub color: Color,
+    pub start_size: f32,
+    pub end_size: f32,
+    /// Seconds since the particle was emitted.
+    pub age: f32,
+    pub lifetime: f32,
+}
+
+impl Particle {
+    pub fn alive(&self) -> bool {
+        self.age < self.lifetime
+    }
+
+    /// `0.0` when emitted, `1.0` when it dies. Always `1.0` without a lifetime.
+    pub fn progress(&self) -> f32 {
+        if self.lifetime <= 0.0 {
+            return 1.0;
+        }
+
+        (self.age / self.lifetime).min(1.0)
+    }
+
+    pub fn size(&self) -> f32 {
+        self.start_size + (self.end_size - self.start_size) * self.progress()
+    }
+
+    /// The emitted color, fading out to transparent over its life.
+    pub fn current_color(&self) -> Color {
+        Color {
+            a: (self.color.a as f32 * (1.0 - self.progress())) as u8,
+            ..self.color.clone()
+        }
+    }
+}
+
+/// A fixed pool of particles. Emitting never allocates; bursts are cut short when
+/// every particle is alive.
+pub struct Particles {
+    pool: Vec<Particle>,
+    rng: Xorshift,
+}
+
+impl Particles {
+    /// Seeded from the clock, so every run looks different.
+    pub fn new(capacity: usize) -> Particles {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.subsec_nanos())
+            .unwrap_or(0);
+
+        Particles::with_seed(capacity, seed)
+    }
+
+    /// The same seed emits the same bursts.
+    pub fn with_seed(capacity: usize, seed: u32) -> Particles {
+        let pool = (0..capacity).map(|_| Particle {
+            position: Vector2::default(),
+            velocity: Vector2::default(),
+            gravity: Vector2::default(),
+            color: Color::default(),
+            start_size: 0.0,
+            end_size: 0.0,
+            age: 0.0,
+            lifetime: 0.0,
+        }).collect();
+
+        Particles {
+            pool,
+            rng: Xorshift::new(seed),
+        }
+    }
+
+    /// Fires `emitter.count` particles from `position`, spread around `direction`.
+    pub fn emit(&mut self, emitter: &Emitter, position: &Vector2, direction: &Vector2) {
+        let angle = direction.y.atan2(direction.x);
+        let mut remaining = emitter.count;
+
+        for particle in self.pool.iter_mut().filter(|particle| !particle.alive()) {
+            if remaining == 0 {
+                break;
+            }
+            remaining -= 1;
+
+            let speed = self.rng.range(emitter.speed);
+            let direction = Vector2 { x: 1.0, y: 0.0 }.rotate(angle + self.rng.range((-emitter.spread, emitter.spread)));
+
+            *particle = Particle {
+                position: *position,
+                velocity: direction * speed,
+                gravity: emitter.gravity,
+                color: emitter.color.clone(),
+                start_size: emitter.size.0,
+                end_size: emitter.size.1,
+                age: 0.0,
+                lifetime: self.rng.range(emitter.lifetime),
+            };
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        for particle in self.pool.iter_mut().filter(|particle| particle.alive()) {
+            particle.velocity += particle.gravity * delta_time;
+            particle.position += particle.velocity * delta_time;
+            particle.age += delta_time;
+        }
+    }
+
+    /// Kills every particle.
+    pub fn clear(&mut self) {
+        for particle in &mut self.pool {
+            particle.age = particle.lifetime;
+        }
+    }
+
+    pub fn alive(&self) -> impl Iterator<Item = &Particle> {
+        self.pool.iter().filter(|particle| particle.alive())
+    }
+
+    pub fn draw<R: Renderer>(&self, d: &mut R) {
+        for particle in self.alive() {
+            d.draw_circle_v(&particle.position, particle.size() / 2.0, &particle.current_color());
+        }
+    }
+}
+
+/// A xorshift32 generator: fast, small, and plenty random for particles.
+struct Xorshift {
+    state: u32,
+}
+
+impl Xorshift {
+    fn new(seed: u32) -> Xorshift {
+        // Xorshift never leaves a zero state.
+        Xorshift { state: seed.max(1) }
+    }
+
+    fn next(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+
+        x
+    }
+
+    /// Uniform in `min..max`.
+    fn range(&mut self, (min, max): (f32, f32)) -> f32 {
+        // The top 24 bits fit an f32 mantissa exactly.
+        min + (max - min) * (self.next() >> 8) as f32 / (1 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Emitter, Particle, Particles};
+    use crate::raylib::{Color, Vector2};
+
+    const EMITTER: Emitter = Emitter {
+        count: 3,
+        speed: (10.0, 20.0),
+        spread: 0.5,
+        lifetime: (1.0, 2.0),
+        gravity: Vector2 { x: 0.0, y: 10.0 },
+        size: (4.0, 0.0),
+        color: Color { r: 255, g: 255, b: 255, a: 255 },
+    };
+
+    const RIGHT: Vector2 = Vector2 { x: 1.0, y: 0.0 };
+
+    #[test]
+    fn emit_fires_count_particles_from_position() {
+        let mut particles = Particles::with_seed(10, 7);
+        let position = Vector2 { x: 5.0, y: -3.0 };
+
+        particles.emit(&EMITTER, &position, &RIGHT);
+
+        assert_eq!(particles.alive().count(), EMITTER.count);
+
+        for particle in particles.alive() {
+            assert_eq!(particle.position, position);
+            assert_eq!(particle.age, 0.0);
+            assert!(particle.lifetime >= EMITTER.lifetime.0 && particle.lifetime < EMITTER.lifetime.1);
+
+            let speed = particle.velocity.length();
+            assert!(speed >= EMITTER.speed.0 - 0.001 && speed < EMITTER.speed.1 + 0.001);
+            assert!(Vector2::angle(&particle.velocity, &RIGHT) <= EMITTER.spread + 0.001);
+        }
+    }
+
+    #[test]
+    fn emit_is_repeatable_from_a_seed() {
+        let mut a = Particles::with_seed(10, 42);
+        let mut b = Particles::with_seed(10, 42);
+
+        a.emit(&EMITTER, &Vector2::default(), &RIGHT);
+        b.emit(&EMITTER, &Vector2::default(), &RIGHT);
+
+        let velocities = |particles: &Particles| particles.alive().map(|particle| particle.velocity).collect::<Vec<_>>();
+        assert_eq!(velocities(&a), velocities(&b));
+    }
+
+    #[test]
+    fn emit_is_cut_short_at_capacity_and_reuses_dead_particles() {
+        let mut particles = Particles::with_seed(4, 1);
+
+        particles.emit(&EMITTER, &Vector2::default(), &RIGHT);
+        particles.emit(&EMITTER, &Vector2::default(), &RIGHT);
+        assert_eq!(particles.alive().count(), 4);
+
+        // Past the longest lifetime, every particle is free again.
+        particles.update(EMITTER.lifetime.1);
+        assert_eq!(particles.alive().count(), 0);
+
+        let position = Vector2 { x: 1.0, y: 1.0 };
+        particles.emit(&EMITTER, &position, &RIGHT);
+        assert_eq!(particles.alive().count(), EMITTER.count);
+        assert!(particles.alive().all(|particle| particle.position == position && particle.age == 0.0));
+    }
+
+    #[test]
+    fn update_ages_and_moves_particles() {
+        let mut particles = Particles::with_seed(10, 3);
+        particles.emit(&EMITTER, &Vector2::default(), &RIGHT);
+
+        let velocities: Vec<Vector2> = particles.alive().map(|particle| particle.velocity).collect();
+        particles.update(0.5);
+
+        for (particle, velocity) in particles.alive().zip(velocities) {
+            let velocity = velocity + EMITTER.gravity * 0.5;
+
+            assert_eq!(particle.age, 0.5);
+            assert_eq!(particle.velocity, velocity);
+            assert_eq!(particle.position, velocity * 0.5);
+        }
+    }
+
+    #[test]
+    fn clear_kills_every_particle() {
+        let mut particles = Particles::with_seed(10, 5);
+        particles.emit(&EMITTER, &Vector2::default(), &RIGHT);
+
+        particles.clear();
+
+        assert_eq!(particles.alive().count(), 0);
+    }
+
+    #[test]
+    fn progress_without_lifetime_is_complete() {
+        let particle = Particle {
+            position: Vector2::default(),
+            velocity: Vector2::default(),
+            gravity: Vector2::default(),
+            color: Color::default(),
+            start_size: 4.0,
+            end_size: 0.0,
+            age: 0.0,
+            lifetime: 0.0,
+        };
+
+        assert_eq!(particle.progress(), 1.0);
+        assert_eq!(particle.size(), 0.0);
+    }
+}
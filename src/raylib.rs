@@ -1,9 +1,147 @@
 extern crate libc;
-use std::ffi::CString;
+use std::borrow::Cow;
+use std::error;
+use std::ffi::{CStr, CString, NulError};
+use std::fmt;
+use std::marker::PhantomData;
 use std::ops;
-use std::time::Instant;
+use std::slice;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Raw declarations generated by `build.rs` from `lib/raylib/parser/output/raylib_api.json`.
+#[allow(non_snake_case, non_camel_case_types, clippy::too_many_arguments)]
+pub mod ffi {
+    pub use super::{Color, Rectangle, Vector2};
+
+    include!(concat!(env!("OUT_DIR"), "/raylib_ffi.rs"));
+}
+
+/// Safe wrappers generated by `build.rs`.
+///
+/// Everything here is re-exported from this module. Items written by hand below
+/// take precedence over generated items with the same name.
+#[allow(dead_code, unused_imports, clippy::approx_constant, clippy::clone_on_copy, clippy::excessive_precision, clippy::too_many_arguments)]
+mod generated {
+    use std::ffi::CStr;
+
+    use super::{ffi, sealed};
+    use super::{Color, Rectangle, Vector2, ToCStr};
+
+    include!(concat!(env!("OUT_DIR"), "/raylib_safe.rs"));
+}
+
+pub use self::generated::*;
+
+/// Keeps `RaylibDraw` and `RaylibWindow` to the handles below. Their methods call
+/// into raylib, which is only safe with the window open, or inside a drawing scope.
+mod sealed {
+    pub trait Sealed {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// C strings end at the first NUL, so text with one inside cannot be passed to raylib.
+    InteriorNul { position: usize },
+    WindowAlreadyOpen,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InteriorNul { position } => write!(f, "text has a NUL byte at {}", position),
+            Error::WindowAlreadyOpen => write!(f, "a window is already open"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<NulError> for Error {
+    fn from(error: NulError) -> Error {
+        Error::InteriorNul { position: error.nul_position() }
+    }
+}
+
+/// Text that can be handed to raylib. `CStr` and `CString` are passed without copying,
+/// so prefer them for text drawn every frame.
+pub trait ToCStr {
+    fn to_c_str(&self) -> Result<Cow<'_, CStr>, Error>;
+
+    /// Strips NUL bytes instead of failing.
+    fn to_c_str_lossy(&self) -> Cow<'_, CStr>;
+}
+
+impl ToCStr for str {
+    fn to_c_str(&self) -> Result<Cow<'_, CStr>, Error> {
+        Ok(Cow::Owned(CString::new(self)?))
+    }
+
+    fn to_c_str_lossy(&self) -> Cow<'_, CStr> {
+        let bytes: Vec<u8> = self.bytes().filter(|b| *b != 0).collect();
+
+        Cow::Owned(CString::new(bytes).unwrap())
+    }
+}
+
+impl ToCStr for String {
+    fn to_c_str(&self) -> Result<Cow<'_, CStr>, Error> {
+        self.as_str().to_c_str()
+    }
+
+    fn to_c_str_lossy(&self) -> Cow<'_, CStr> {
+        self.as_str().to_c_str_lossy()
+    }
+}
+
+impl ToCStr for CStr {
+    fn to_c_str(&self) -> Result<Cow<'_, CStr>, Error> {
+        Ok(Cow::Borrowed(self))
+    }
+
+    fn to_c_str_lossy(&self) -> Cow<'_, CStr> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl ToCStr for CString {
+    fn to_c_str(&self) -> Result<Cow<'_, CStr>, Error> {
+        Ok(Cow::Borrowed(self.as_c_str()))
+    }
+
+    fn to_c_str_lossy(&self) -> Cow<'_, CStr> {
+        Cow::Borrowed(self.as_c_str())
+    }
+}
+
+/// A C string rebuilt only when its key changes, e.g. a score drawn every frame.
+pub struct CachedText<K: PartialEq> {
+    key: Option<K>,
+    text: CString,
+}
+
+impl<K: PartialEq> Default for CachedText<K> {
+    fn default() -> Self {
+        CachedText {
+            key: None,
+            text: CString::default(),
+        }
+    }
+}
+
+impl<K: PartialEq> CachedText<K> {
+    /// Calls `render` only when `key` differs from the last call.
+    pub fn get(&mut self, key: K, render: impl FnOnce(&K) -> String) -> &CStr {
+        if self.key.as_ref() != Some(&key) {
+            self.text = render(&key).to_c_str_lossy().into_owned();
+            self.key = Some(key);
+        }
+
+        &self.text
+    }
+}
 
 #[repr(C)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -34,6 +172,7 @@ impl Default for Color {
 }
 
 #[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Vector2 {
     pub x: f32,
     pub y: f32,
@@ -48,15 +187,6 @@ impl Default for Vector2 {
     }
 }
 
-impl Clone for Vector2 {
-    fn clone(&self) -> Vector2 {
-        Vector2 {
-            x: self.x,
-            y: self.y,
-        }
-    }
-}
-
 impl ops::Add<Vector2> for Vector2 {
     type Output = Vector2;
 
@@ -75,6 +205,35 @@ impl ops::AddAssign<Vector2> for Vector2 {
     }
 }
 
+impl ops::Sub<Vector2> for Vector2 {
+    type Output = Vector2;
+
+    fn sub(self, rhs: Vector2) -> Vector2 {
+        Vector2 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl ops::SubAssign<Vector2> for Vector2 {
+    fn sub_assign(&mut self, rhs: Vector2) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl ops::Neg for Vector2 {
+    type Output = Vector2;
+
+    fn neg(self) -> Vector2 {
+        Vector2 {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
 impl ops::Mul<f32> for Vector2 {
     type Output = Vector2;
 
@@ -86,30 +245,125 @@ impl ops::Mul<f32> for Vector2 {
     }
 }
 
+impl ops::MulAssign<f32> for Vector2 {
+    fn mul_assign(&mut self, rhs: f32) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+impl ops::Div<f32> for Vector2 {
+    type Output = Vector2;
+
+    fn div(self, rhs: f32) -> Vector2 {
+        Vector2 {
+            x: self.x / rhs,
+            y: self.y / rhs,
+        }
+    }
+}
+
+impl ops::DivAssign<f32> for Vector2 {
+    fn div_assign(&mut self, rhs: f32) {
+        self.x /= rhs;
+        self.y /= rhs;
+    }
+}
+
+/// Below this length a vector has no usable direction.
+const VECTOR2_EPSILON: f32 = 0.00001;
+
 impl Vector2 {
     pub fn length(&self) -> f32 {
-        (self.x * self.x + self.y * self.y).sqrt()
+        self.length_sqr().sqrt()
+    }
+
+    pub fn length_sqr(&self) -> f32 {
+        self.x * self.x + self.y * self.y
     }
 
+    /// Leaves a zero-length vector unchanged.
     pub fn normalize(&mut self) {
+        *self = self.normalized();
+    }
+
+    /// A unit vector in the same direction, or the vector itself when it has zero length.
+    pub fn normalized(&self) -> Vector2 {
+        let length = self.length();
+
+        if length < VECTOR2_EPSILON {
+            return *self;
+        }
+
+        *self / length
+    }
+
+    pub fn dot(&self, other: &Vector2) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the 3D cross product: positive when `other` is clockwise
+    /// from `self` on screen, since y points down.
+    pub fn cross(&self, other: &Vector2) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn distance(&self, other: &Vector2) -> f32 {
+        (*other - *self).length()
+    }
+
+    /// `amount` is not clamped, so values outside `0.0..=1.0` extrapolate.
+    pub fn lerp(&self, other: &Vector2, amount: f32) -> Vector2 {
+        *self + (*other - *self) * amount
+    }
+
+    /// Mirrors the vector about a surface with the given normal, which should be a unit vector.
+    pub fn reflect(&self, normal: &Vector2) -> Vector2 {
+        *self - *normal * (2.0 * self.dot(normal))
+    }
+
+    /// Rotates by `angle` radians, clockwise on screen.
+    pub fn rotate(&self, angle: f32) -> Vector2 {
+        let (sin, cos) = angle.sin_cos();
+
+        Vector2 {
+            x: self.x * cos - self.y * sin,
+            y: self.x * sin + self.y * cos,
+        }
+    }
+
+    /// Shortens the vector to `max` if it is longer, keeping its direction. A negative
+    /// `max` gives the zero vector rather than flipping it.
+    pub fn clamp_length(&self, max: f32) -> Vector2 {
+        if max <= 0.0 {
+            return Vector2::default();
+        }
+
         let length = self.length();
 
-        if length < 0.00001 {
-            return;
+        if length <= max || length < VECTOR2_EPSILON {
+            return *self;
         }
 
-        self.x /= length;
-        self.y /= length;
+        *self * (max / length)
     }
 
+    /// The unsigned angle between two vectors in radians, `0.0..=PI`.
+    /// Zero when either vector has zero length.
     pub fn angle(a: &Vector2, b: &Vector2) -> f32 {
-        let dot = a.x * b.x + a.y * b.y;
-        
-        dot.acos()
+        let lengths = a.length() * b.length();
+
+        if lengths < VECTOR2_EPSILON {
+            return 0.0;
+        }
+
+        // Rounding can push the cosine just past 1.0, where acos returns NaN.
+        (a.dot(b) / lengths).clamp(-1.0, 1.0).acos()
     }
 }
 
 #[repr(C)]
+#[derive(Debug, PartialEq)]
 pub struct Rectangle {
     pub x: f32,
     pub y: f32,
@@ -147,245 +401,1358 @@ pub struct Circle {
 impl Circle {
     pub fn from(position: &Vector2, radius: f32) -> Circle {
         Circle {
-            center: position.clone(),
+            center: *position,
             radius,
         }
     }
 }
 
-pub struct Time {
-    start: Instant,
-    old_elapsed: f32,
+impl Default for Camera2D {
+    /// Shows the world as is: no offset, rotation or zoom.
+    fn default() -> Camera2D {
+        Camera2D {
+            offset: Vector2::default(),
+            target: Vector2::default(),
+            rotation: 0.0,
+            zoom: 1.0,
+        }
+    }
+}
+
+/// An image in CPU memory, unloaded when dropped.
+pub struct Image {
+    raw: ffi::Image,
 }
 
-impl Default for Time {
-    fn default() -> Time {
-        Time {
-            start: Instant::now(),
-            old_elapsed: 0.0,
+impl Image {
+    pub fn load(file_name: &str) -> Option<Image> {
+        let c_file_name = file_name.to_c_str().ok()?;
+
+        let raw = unsafe {
+            ffi::LoadImage(c_file_name.as_ptr())
+        };
+
+        Image::from_raw(raw)
+    }
+
+    /// `file_type` is the extension including the dot, e.g. `".png"`.
+    pub fn load_from_memory(file_type: &str, data: &[u8]) -> Option<Image> {
+        let c_file_type = file_type.to_c_str().ok()?;
+
+        let raw = unsafe {
+            ffi::LoadImageFromMemory(c_file_type.as_ptr(), data.as_ptr(), data.len() as i32)
+        };
+
+        Image::from_raw(raw)
+    }
+
+    /// Reads back what has been drawn to the window so far. Draw calls are
+    /// batched, so call `flush_draw_batch` first when capturing mid-frame.
+    pub fn from_screen() -> Option<Image> {
+        let raw = unsafe {
+            ffi::LoadImageFromScreen()
+        };
+
+        Image::from_raw(raw)
+    }
+
+    /// Reads back a render texture, flipped the right way up.
+    pub fn from_render_texture(target: &RenderTexture) -> Option<Image> {
+        let mut raw = unsafe {
+            ffi::LoadImageFromTexture(target.raw.texture.clone())
+        };
+
+        if !raw.data.is_null() {
+            unsafe {
+                ffi::ImageFlipVertical(&mut raw);
+            }
         }
+
+        Image::from_raw(raw)
     }
-}
 
-impl Time {
-    pub fn delta_time(&mut self) -> f32 {
-        let duration = self.start.elapsed();
-        let current = duration.as_secs_f32();
-        let delta = current - self.old_elapsed;
+    fn from_raw(raw: ffi::Image) -> Option<Image> {
+        if raw.data.is_null() {
+            return None;
+        }
+
+        Some(Image { raw })
+    }
 
-        self.old_elapsed = current;
+    /// The format follows the extension of `file_name`, e.g. `.png`.
+    pub fn export(&self, file_name: &str) -> bool {
+        let c_file_name = match file_name.to_c_str() {
+            Ok(c_file_name) => c_file_name,
+            Err(_) => return false,
+        };
 
-        delta
+        unsafe {
+            ffi::ExportImage(self.raw.clone(), c_file_name.as_ptr())
+        }
     }
-}
 
-pub enum Key {
-    P = 80,
-    Space = 32,
-    Enter = 257,
-    Right = 262,
-    Left = 263,
-    Down = 264,
-    Up = 265,
-}
+    pub fn width(&self) -> i32 {
+        self.raw.width
+    }
 
-pub const BLACK: Color = Color { r: 0, g: 0, b: 0, a: 255 };
-pub const DARKGRAY: Color = Color { r: 80, g: 80, b: 80, a: 255 };
-pub const LIGHTGRAY: Color = Color { r: 200, g: 200, b: 200, a: 255 };
-pub const GRAY: Color = Color { r: 130, g: 130, b: 130, a: 255 };
-pub const MAROON: Color = Color { r: 190, g: 33, b: 55, a: 255 };
-pub const RAYWHITE: Color = Color { r: 245, g: 245, b: 245, a: 255 };
+    pub fn height(&self) -> i32 {
+        self.raw.height
+    }
 
-pub enum SceneResult {
-    OnGoing,
-    Ended,
-}
+    /// The pixels row by row from the top, 4 bytes each, or `None` when the image
+    /// is in another format. Images read back from the GPU are RGBA.
+    pub fn rgba(&self) -> Option<&[u8]> {
+        if self.raw.format != PixelFormat::UncompressedR8g8b8a8 as i32 {
+            return None;
+        }
 
-pub trait Scene {
-    fn init(&mut self) {}
-    fn frame(&mut self, delta_time: f32) -> SceneResult;
-}
+        let len = self.raw.width as usize * self.raw.height as usize * 4;
+
+        Some(unsafe { slice::from_raw_parts(self.raw.data as *const u8, len) })
+    }
 
-pub struct SceneManager {
-    scenes: Vec<Box<dyn Scene>>,
-    current_scene: usize,
+    pub fn raw(&self) -> &ffi::Image {
+        &self.raw
+    }
 }
 
-impl SceneManager {
-    pub fn new() -> Self {
-        Self {
-            scenes: Vec::new(),
-            current_scene: 0,
+impl Drop for Image {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::UnloadImage(self.raw.clone());
         }
     }
+}
+
+/// A texture in GPU memory, unloaded when dropped.
+///
+/// Drop textures before `close_window`, while the OpenGL context still exists.
+pub struct Texture {
+    raw: ffi::Texture,
+}
+
+pub type Texture2D = Texture;
 
-    pub fn add(&mut self, scene: Box<dyn Scene>) {
-        self.scenes.push(scene);
+impl Texture {
+    pub fn load(file_name: &str) -> Option<Texture> {
+        let c_file_name = file_name.to_c_str().ok()?;
+
+        let raw = unsafe {
+            ffi::LoadTexture(c_file_name.as_ptr())
+        };
+
+        Texture::from_raw(raw)
     }
 
-    pub fn set(&mut self, index: usize) {
-        self.current_scene = index;
+    /// `file_type` is the extension including the dot, e.g. `".png"`.
+    pub fn load_from_memory(file_type: &str, data: &[u8]) -> Option<Texture> {
+        Texture::from_image(&Image::load_from_memory(file_type, data)?)
     }
 
-    pub fn run(&mut self) {
-        'outer: loop {
-            self.scenes[self.current_scene].init();
+    pub fn from_image(image: &Image) -> Option<Texture> {
+        let raw = unsafe {
+            ffi::LoadTextureFromImage(image.raw.clone())
+        };
 
-            let mut time = Time::default();
+        Texture::from_raw(raw)
+    }
 
-            'inner: loop {
-                let delta_time = time.delta_time();
+    fn from_raw(raw: ffi::Texture) -> Option<Texture> {
+        if raw.id == 0 {
+            return None;
+        }
 
-                begin_drawing();
+        Some(Texture { raw })
+    }
 
-                let scene_result = self.scenes[self.current_scene].frame(delta_time);
+    pub fn width(&self) -> i32 {
+        self.raw.width
+    }
 
-                end_drawing();
+    pub fn height(&self) -> i32 {
+        self.raw.height
+    }
 
-                if window_should_close() {
-                    break 'outer;
-                }
+    /// The whole texture as a source rectangle.
+    pub fn bounds(&self) -> Rectangle {
+        Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: self.raw.width as f32,
+            height: self.raw.height as f32,
+        }
+    }
 
-                if let SceneResult::Ended = scene_result {
-                    break 'inner;
-                }
-            }
+    /// For generated wrappers such as `draw_texture_n_patch`.
+    pub fn raw(&self) -> &ffi::Texture {
+        &self.raw
+    }
+}
 
-            self.current_scene += 1;
+impl Drop for Texture {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::UnloadTexture(self.raw.clone());
         }
     }
 }
 
-#[link(name = "raylib", kind = "static")]
-extern "C" {
-    fn InitWindow(width: i32, height: i32, title: *const libc::c_char);
-    fn SetTargetFPS(fps: i32);
-    fn WindowShouldClose() -> bool;
-    fn BeginDrawing();
-    fn EndDrawing();
-    fn ClearBackground(color: Color);
-    fn DrawText(text: *const libc::c_char, x: i32, y: i32, font_size: i32, color: Color);
-    fn Raylib_CloseWindow();
+/// A framebuffer that can be drawn into like the screen, unloaded when dropped.
+///
+/// Drop render textures before `close_window`, while the OpenGL context still exists.
+pub struct RenderTexture {
+    raw: ffi::RenderTexture,
+}
 
-    fn MeasureText(text: *const libc::c_char, font_size: i32) -> i32;
+pub type RenderTexture2D = RenderTexture;
 
-    fn IsKeyDown(key: i32) -> bool;
-    fn IsKeyPressed(key: i32) -> bool;
+impl RenderTexture {
+    pub fn load(width: i32, height: i32) -> Option<RenderTexture> {
+        let raw = unsafe {
+            ffi::LoadRenderTexture(width, height)
+        };
 
-    fn DrawCircleV(center: Vector2, radius: f32, color: Color);
-    fn DrawRectangle(x: i32, y: i32, width: i32, height: i32, color: Color);
+        if raw.id == 0 {
+            return None;
+        }
 
-    fn CheckCollisionCircleRec(center: Vector2, radius: f32, rec: Rectangle) -> bool;
+        Some(RenderTexture { raw })
+    }
 
-    fn GuiButton(rect: Rectangle, text: *const libc::c_char) -> bool;
-}
+    pub fn width(&self) -> i32 {
+        self.raw.texture.width
+    }
 
-pub fn init_window(width: i32, height: i32, title: &str) {
-    let c_title = CString::new(title).unwrap();
+    pub fn height(&self) -> i32 {
+        self.raw.texture.height
+    }
 
-    unsafe {
-        InitWindow(width, height, c_title.as_ptr() as *const libc::c_char);
+    /// The color buffer as a source rectangle. The height is negative because
+    /// OpenGL stores framebuffers bottom-up.
+    pub fn bounds(&self) -> Rectangle {
+        Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: self.raw.texture.width as f32,
+            height: -self.raw.texture.height as f32,
+        }
     }
-}
 
-pub fn set_target_fps(fps: i32) {
-    unsafe {
-        SetTargetFPS(fps);
+    pub fn set_filter(&self, filter: TextureFilter) {
+        unsafe {
+            ffi::SetTextureFilter(self.raw.texture.clone(), filter as i32);
+        }
     }
 }
 
-pub fn window_should_close() -> bool {
-    unsafe {
-        WindowShouldClose()
+impl Drop for RenderTexture {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::UnloadRenderTexture(self.raw.clone());
+        }
     }
 }
 
-pub fn begin_drawing() {
-    unsafe {
-        BeginDrawing();
-    }
+/// A compiled shader program, unloaded when dropped.
+///
+/// Drop shaders before `close_window`, while the OpenGL context still exists.
+pub struct Shader {
+    raw: ffi::Shader,
 }
 
-pub fn end_drawing() {
-    unsafe {
-        EndDrawing();
+impl Shader {
+    /// Either stage may be `None` to use raylib's default one.
+    pub fn load(vs_file_name: Option<&str>, fs_file_name: Option<&str>) -> Option<Shader> {
+        let c_vs_file_name = vs_file_name.map(|name| name.to_c_str()).transpose().ok()?;
+        let c_fs_file_name = fs_file_name.map(|name| name.to_c_str()).transpose().ok()?;
+
+        let raw = unsafe {
+            ffi::LoadShader(optional_ptr(&c_vs_file_name), optional_ptr(&c_fs_file_name))
+        };
+
+        Shader::from_raw(raw)
     }
-}
 
-pub fn clear_background(color: &Color) {
-    let c_color = color.clone();
+    /// Either stage may be `None` to use raylib's default one.
+    pub fn load_from_memory(vs_code: Option<&str>, fs_code: Option<&str>) -> Option<Shader> {
+        let c_vs_code = vs_code.map(|code| code.to_c_str()).transpose().ok()?;
+        let c_fs_code = fs_code.map(|code| code.to_c_str()).transpose().ok()?;
 
-    unsafe {
-        ClearBackground(c_color);
+        let raw = unsafe {
+            ffi::LoadShaderFromMemory(optional_ptr(&c_vs_code), optional_ptr(&c_fs_code))
+        };
+
+        Shader::from_raw(raw)
     }
-}
 
-pub fn draw_text(text: &str, x: i32, y: i32, font_size: i32, color: &Color) {
-    let c_text = CString::new(text).unwrap();
-    let c_color = color.clone();
+    fn from_raw(raw: ffi::Shader) -> Option<Shader> {
+        let ready = unsafe {
+            ffi::IsShaderReady(raw.clone())
+        };
 
-    unsafe {
-        DrawText(c_text.as_ptr() as *const libc::c_char, x, y, font_size, c_color);
+        // raylib falls back to its default shader when both stages fail to compile.
+        if !ready || raw.id == unsafe { rlGetShaderIdDefault() } {
+            return None;
+        }
+
+        Some(Shader { raw })
     }
-}
 
-pub fn close_window() {
-    unsafe {
-        Raylib_CloseWindow();
+    /// `None` when the uniform does not exist or was optimized out by the compiler.
+    pub fn location(&self, uniform_name: &str) -> Option<i32> {
+        let c_uniform_name = uniform_name.to_c_str().ok()?;
+
+        let location = unsafe {
+            ffi::GetShaderLocation(self.raw.clone(), c_uniform_name.as_ptr())
+        };
+
+        if location < 0 {
+            return None;
+        }
+
+        Some(location)
     }
-}
 
-pub fn measure_text(text: &str, font_size: i32) -> i32 {
-    let c_text = CString::new(text).unwrap();
+    pub fn set_value<T: ShaderValue>(&self, location: i32, value: T) {
+        unsafe {
+            ffi::SetShaderValue(self.raw.clone(), location, &value as *const T as *const libc::c_void, T::UNIFORM_TYPE as i32);
+        }
+    }
 
-    unsafe {
-        MeasureText(c_text.as_ptr() as *const libc::c_char, font_size)
+    /// Looks the uniform up by name; does nothing if it does not exist.
+    pub fn set_uniform<T: ShaderValue>(&self, uniform_name: &str, value: T) {
+        if let Some(location) = self.location(uniform_name) {
+            self.set_value(location, value);
+        }
+    }
+
+    pub fn set_texture(&self, location: i32, texture: &Texture) {
+        unsafe {
+            ffi::SetShaderValueTexture(self.raw.clone(), location, texture.raw.clone());
+        }
     }
 }
 
-pub fn is_key_down(key: Key) -> bool {
-    unsafe {
-        IsKeyDown(key as i32)
+impl Drop for Shader {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::UnloadShader(self.raw.clone());
+        }
     }
 }
 
-pub fn is_key_pressed(key: Key) -> bool {
-    unsafe {
-        IsKeyPressed(key as i32)
+fn optional_ptr(c_str: &Option<Cow<'_, CStr>>) -> *const libc::c_char {
+    match c_str {
+        Some(c_str) => c_str.as_ptr(),
+        None => std::ptr::null(),
     }
 }
 
-pub fn draw_circle_v(center: &Vector2, radius: f32, color: &Color) {
-    let c_center = center.clone();
-    let c_color = color.clone();
+/// A value that can be uploaded as a shader uniform.
+///
+/// # Safety
+///
+/// The type must have the memory layout of `UNIFORM_TYPE`.
+pub unsafe trait ShaderValue {
+    const UNIFORM_TYPE: ShaderUniformDataType;
+}
+
+unsafe impl ShaderValue for f32 {
+    const UNIFORM_TYPE: ShaderUniformDataType = ShaderUniformDataType::Float;
+}
 
-    unsafe {
-        DrawCircleV(c_center, radius, c_color);
-    }
+unsafe impl ShaderValue for Vector2 {
+    const UNIFORM_TYPE: ShaderUniformDataType = ShaderUniformDataType::Vec2;
 }
 
-pub fn draw_rectangle(x: i32, y: i32, width: i32, height: i32, color: &Color) {
-    let c_color = color.clone();
+unsafe impl ShaderValue for [f32; 2] {
+    const UNIFORM_TYPE: ShaderUniformDataType = ShaderUniformDataType::Vec2;
+}
 
-    unsafe {
-        DrawRectangle(x, y, width, height, c_color);
-    }
+unsafe impl ShaderValue for [f32; 3] {
+    const UNIFORM_TYPE: ShaderUniformDataType = ShaderUniformDataType::Vec3;
 }
 
-pub fn check_collision_circle_rec(circle: &Circle, rec: &Rectangle) -> bool {
-    let c_center = circle.center.clone();
-    let c_rec = rec.clone();
+unsafe impl ShaderValue for [f32; 4] {
+    const UNIFORM_TYPE: ShaderUniformDataType = ShaderUniformDataType::Vec4;
+}
 
-    unsafe {
-        CheckCollisionCircleRec(c_center, circle.radius, c_rec)
-    }
+unsafe impl ShaderValue for i32 {
+    const UNIFORM_TYPE: ShaderUniformDataType = ShaderUniformDataType::Int;
 }
 
-pub fn gui_button(rect: &Rectangle, text: &str) -> bool {
-    let c_rect = rect.clone();
-    let c_text = CString::new(text).unwrap();
+unsafe impl ShaderValue for [i32; 2] {
+    const UNIFORM_TYPE: ShaderUniformDataType = ShaderUniformDataType::Ivec2;
+}
 
-    unsafe {
-        GuiButton(c_rect, c_text.as_ptr() as *const libc::c_char)
+/// A font atlas in GPU memory, unloaded when dropped.
+pub struct Font {
+    raw: ffi::Font,
+}
+
+impl Font {
+    /// raylib's built-in bitmap font, used by `draw_text`. Dropping it is a no-op.
+    pub fn get_default() -> Font {
+        let raw = unsafe {
+            ffi::GetFontDefault()
+        };
+
+        Font { raw }
+    }
+
+    pub fn load(file_name: &str) -> Option<Font> {
+        let c_file_name = file_name.to_c_str().ok()?;
+
+        let raw = unsafe {
+            ffi::LoadFont(c_file_name.as_ptr())
+        };
+
+        Font::from_raw(raw)
+    }
+
+    /// Rasterizes a TTF/OTF font at `font_size` pixels. An empty `codepoints` loads ASCII 32..126.
+    pub fn load_ex(file_name: &str, font_size: i32, codepoints: &[i32]) -> Option<Font> {
+        let c_file_name = file_name.to_c_str().ok()?;
+        let c_codepoints = if codepoints.is_empty() {
+            std::ptr::null_mut()
+        } else {
+            codepoints.as_ptr() as *mut i32
+        };
+
+        let raw = unsafe {
+            ffi::LoadFontEx(c_file_name.as_ptr(), font_size, c_codepoints, codepoints.len() as i32)
+        };
+
+        Font::from_raw(raw)
+    }
+
+    /// raylib falls back to the default font instead of failing.
+    fn from_raw(raw: ffi::Font) -> Option<Font> {
+        let default = unsafe {
+            ffi::GetFontDefault()
+        };
+
+        if raw.texture.id == 0 || raw.texture.id == default.texture.id {
+            return None;
+        }
+
+        Some(Font { raw })
+    }
+
+    /// The size the glyphs were rasterized at.
+    pub fn base_size(&self) -> i32 {
+        self.raw.base_size
+    }
+
+    /// Scaled fonts look smoother with `TextureFilter::Bilinear`.
+    pub fn set_filter(&self, filter: TextureFilter) {
+        unsafe {
+            ffi::SetTextureFilter(self.raw.texture.clone(), filter as i32);
+        }
+    }
+}
+
+impl Drop for Font {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::UnloadFont(self.raw.clone());
+        }
+    }
+}
+
+/// The audio device, closed when dropped.
+///
+/// Sounds and music do not keep the device alive, so drop them first.
+pub struct AudioDevice {
+    _private: (),
+}
+
+impl AudioDevice {
+    /// Returns `None` when there is no usable device, e.g. on a headless machine.
+    pub fn init() -> Option<AudioDevice> {
+        let ready = unsafe {
+            ffi::InitAudioDevice();
+            ffi::IsAudioDeviceReady()
+        };
+
+        if !ready {
+            return None;
+        }
+
+        Some(AudioDevice { _private: () })
+    }
+}
+
+impl Drop for AudioDevice {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::CloseAudioDevice();
+        }
+    }
+}
+
+/// Decoded audio samples in CPU memory, unloaded when dropped.
+pub struct Wave {
+    raw: ffi::Wave,
+}
+
+impl Wave {
+    pub fn load(file_name: &str) -> Option<Wave> {
+        let c_file_name = file_name.to_c_str().ok()?;
+
+        let raw = unsafe {
+            ffi::LoadWave(c_file_name.as_ptr())
+        };
+
+        Wave::from_raw(raw)
+    }
+
+    /// `file_type` is the extension including the dot, e.g. `".wav"`.
+    pub fn load_from_memory(file_type: &str, data: &[u8]) -> Option<Wave> {
+        let c_file_type = file_type.to_c_str().ok()?;
+
+        let raw = unsafe {
+            ffi::LoadWaveFromMemory(c_file_type.as_ptr(), data.as_ptr(), data.len() as i32)
+        };
+
+        Wave::from_raw(raw)
+    }
+
+    fn from_raw(raw: ffi::Wave) -> Option<Wave> {
+        if raw.data.is_null() {
+            return None;
+        }
+
+        Some(Wave { raw })
+    }
+}
+
+impl Drop for Wave {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::UnloadWave(self.raw.clone());
+        }
+    }
+}
+
+/// A sound effect held fully in memory, unloaded when dropped.
+pub struct Sound {
+    raw: ffi::Sound,
+}
+
+impl Sound {
+    pub fn load(file_name: &str) -> Option<Sound> {
+        let c_file_name = file_name.to_c_str().ok()?;
+
+        let raw = unsafe {
+            ffi::LoadSound(c_file_name.as_ptr())
+        };
+
+        Sound::from_raw(raw)
+    }
+
+    pub fn from_wave(wave: &Wave) -> Option<Sound> {
+        let raw = unsafe {
+            ffi::LoadSoundFromWave(wave.raw.clone())
+        };
+
+        Sound::from_raw(raw)
+    }
+
+    /// Fails without an audio device, since the buffer lives on the device side.
+    fn from_raw(raw: ffi::Sound) -> Option<Sound> {
+        if raw.stream.buffer.is_null() {
+            return None;
+        }
+
+        Some(Sound { raw })
+    }
+
+    pub fn play(&self) {
+        unsafe {
+            ffi::PlaySound(self.raw.clone());
+        }
+    }
+
+    pub fn stop(&self) {
+        unsafe {
+            ffi::StopSound(self.raw.clone());
+        }
+    }
+
+    pub fn is_playing(&self) -> bool {
+        unsafe {
+            ffi::IsSoundPlaying(self.raw.clone())
+        }
+    }
+
+    /// 1.0 is full volume.
+    pub fn set_volume(&self, volume: f32) {
+        unsafe {
+            ffi::SetSoundVolume(self.raw.clone(), volume);
+        }
+    }
+
+    /// 1.0 is the original pitch.
+    pub fn set_pitch(&self, pitch: f32) {
+        unsafe {
+            ffi::SetSoundPitch(self.raw.clone(), pitch);
+        }
+    }
+}
+
+impl Drop for Sound {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::UnloadSound(self.raw.clone());
+        }
+    }
+}
+
+/// Streamed music, unloaded when dropped. Call `update` every frame while it plays.
+pub struct Music {
+    raw: ffi::Music,
+}
+
+impl Music {
+    pub fn load(file_name: &str) -> Option<Music> {
+        let c_file_name = file_name.to_c_str().ok()?;
+
+        let raw = unsafe {
+            ffi::LoadMusicStream(c_file_name.as_ptr())
+        };
+
+        if raw.ctx_data.is_null() {
+            return None;
+        }
+
+        Some(Music { raw })
+    }
+
+    pub fn play(&self) {
+        unsafe {
+            ffi::PlayMusicStream(self.raw.clone());
+        }
+    }
+
+    /// Refills the stream buffers.
+    pub fn update(&self) {
+        unsafe {
+            ffi::UpdateMusicStream(self.raw.clone());
+        }
+    }
+
+    pub fn stop(&self) {
+        unsafe {
+            ffi::StopMusicStream(self.raw.clone());
+        }
+    }
+
+    pub fn pause(&self) {
+        unsafe {
+            ffi::PauseMusicStream(self.raw.clone());
+        }
+    }
+
+    pub fn resume(&self) {
+        unsafe {
+            ffi::ResumeMusicStream(self.raw.clone());
+        }
+    }
+
+    pub fn is_playing(&self) -> bool {
+        unsafe {
+            ffi::IsMusicStreamPlaying(self.raw.clone())
+        }
+    }
+
+    /// 1.0 is full volume.
+    pub fn set_volume(&self, volume: f32) {
+        unsafe {
+            ffi::SetMusicVolume(self.raw.clone(), volume);
+        }
+    }
+
+    pub fn set_looping(&mut self, looping: bool) {
+        self.raw.looping = looping;
+    }
+}
+
+impl Drop for Music {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::UnloadMusicStream(self.raw.clone());
+        }
+    }
+}
+
+/// Every raylib key, generated from `KeyboardKey`.
+pub type Key = KeyboardKey;
+
+// raygui and rlgl are compiled into our raylib build but are not covered by raylib_api.json.
+#[link(name = "raylib", kind = "static")]
+extern "C" {
+    fn GuiButton(rect: Rectangle, text: *const libc::c_char) -> bool;
+    fn rlGetShaderIdDefault() -> u32;
+    fn rlDrawRenderBatchActive();
+}
+
+/// Sends batched draw calls to the GPU now instead of at the end of the frame.
+pub fn flush_draw_batch() {
+    unsafe {
+        rlDrawRenderBatchActive();
+    }
+}
+
+/// The open window. raylib keeps its state in globals tied to the thread that
+/// opened the window, so the handle is neither `Send` nor `Sync` and only one
+/// can exist at a time. Dropping it closes the window.
+///
+/// Functions that need the window, such as input queries, are methods of the
+/// handle. Generated ones come from the `RaylibWindow` trait; the methods
+/// below take precedence over them.
+pub struct RaylibHandle {
+    _not_send: PhantomData<*const ()>,
+}
+
+static WINDOW_OPEN: AtomicBool = AtomicBool::new(false);
+
+/// Strips NUL bytes from `title`. Panics if a window is already open.
+pub fn init_window(width: i32, height: i32, title: &str) -> RaylibHandle {
+    match open_window(width, height, &title.to_c_str_lossy()) {
+        Ok(rl) => rl,
+        Err(error) => panic!("init_window failed: {}", error),
+    }
+}
+
+pub fn try_init_window(width: i32, height: i32, title: &str) -> Result<RaylibHandle, Error> {
+    open_window(width, height, &title.to_c_str()?)
+}
+
+fn open_window(width: i32, height: i32, title: &CStr) -> Result<RaylibHandle, Error> {
+    if WINDOW_OPEN.swap(true, Ordering::SeqCst) {
+        return Err(Error::WindowAlreadyOpen);
+    }
+
+    unsafe {
+        ffi::InitWindow(width, height, title.as_ptr() as *const libc::c_char);
+    }
+
+    Ok(RaylibHandle {
+        _not_send: PhantomData,
+    })
+}
+
+impl RaylibHandle {
+    /// Starts a frame. The frame is presented when the returned handle is dropped.
+    pub fn begin_drawing(&mut self) -> DrawHandle<'_> {
+        unsafe {
+            ffi::BeginDrawing();
+        }
+
+        DrawHandle {
+            target: DrawTarget::Screen,
+            handle: self,
+        }
+    }
+
+    /// Redirects drawing into `target` until the returned handle is dropped.
+    /// Call this outside `begin_drawing`.
+    pub fn begin_texture_mode<'a>(&'a mut self, target: &'a mut RenderTexture) -> DrawHandle<'a> {
+        unsafe {
+            ffi::BeginTextureMode(target.raw.clone());
+        }
+
+        DrawHandle {
+            target: DrawTarget::Texture,
+            handle: self,
+        }
+    }
+
+    pub fn set_target_fps(&self, fps: i32) {
+        unsafe {
+            ffi::SetTargetFPS(fps);
+        }
+    }
+
+    pub fn window_should_close(&self) -> bool {
+        unsafe {
+            ffi::WindowShouldClose()
+        }
+    }
+
+    /// Strips NUL bytes from `text`.
+    pub fn measure_text<T: ToCStr + ?Sized>(&self, text: &T, font_size: i32) -> i32 {
+        measure_c_text(&text.to_c_str_lossy(), font_size)
+    }
+
+    pub fn try_measure_text<T: ToCStr + ?Sized>(&self, text: &T, font_size: i32) -> Result<i32, Error> {
+        Ok(measure_c_text(&text.to_c_str()?, font_size))
+    }
+
+    /// Strips NUL bytes from `text`.
+    pub fn measure_text_ex<T: ToCStr + ?Sized>(&self, font: &Font, text: &T, font_size: f32, spacing: f32) -> Vector2 {
+        measure_c_text_ex(font, &text.to_c_str_lossy(), font_size, spacing)
+    }
+
+    pub fn try_measure_text_ex<T: ToCStr + ?Sized>(&self, font: &Font, text: &T, font_size: f32, spacing: f32) -> Result<Vector2, Error> {
+        Ok(measure_c_text_ex(font, &text.to_c_str()?, font_size, spacing))
+    }
+
+    pub fn is_key_down(&self, key: Key) -> bool {
+        unsafe {
+            ffi::IsKeyDown(key as i32)
+        }
+    }
+
+    pub fn is_key_pressed(&self, key: Key) -> bool {
+        unsafe {
+            ffi::IsKeyPressed(key as i32)
+        }
+    }
+
+    pub fn is_key_released(&self, key: Key) -> bool {
+        unsafe {
+            ffi::IsKeyReleased(key as i32)
+        }
+    }
+
+    pub fn is_key_up(&self, key: Key) -> bool {
+        unsafe {
+            ffi::IsKeyUp(key as i32)
+        }
+    }
+
+    /// Pops the next key from the pressed queue. Call it in a loop to drain the queue.
+    pub fn get_key_pressed(&self) -> Option<Key> {
+        let key = unsafe {
+            ffi::GetKeyPressed()
+        };
+
+        Key::try_from(key).ok().filter(|key| *key != Key::Null)
+    }
+
+    /// Pops the next unicode character from the typed queue, for text input.
+    pub fn get_char_pressed(&self) -> Option<char> {
+        let codepoint = unsafe {
+            ffi::GetCharPressed()
+        };
+
+        if codepoint <= 0 {
+            return None;
+        }
+
+        char::from_u32(codepoint as u32)
+    }
+
+    pub fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
+        unsafe {
+            ffi::IsMouseButtonPressed(button as i32)
+        }
+    }
+
+    pub fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        unsafe {
+            ffi::IsMouseButtonDown(button as i32)
+        }
+    }
+
+    pub fn is_mouse_button_released(&self, button: MouseButton) -> bool {
+        unsafe {
+            ffi::IsMouseButtonReleased(button as i32)
+        }
+    }
+
+    pub fn is_mouse_button_up(&self, button: MouseButton) -> bool {
+        unsafe {
+            ffi::IsMouseButtonUp(button as i32)
+        }
+    }
+
+    pub fn is_gamepad_button_pressed(&self, gamepad: i32, button: GamepadButton) -> bool {
+        unsafe {
+            ffi::IsGamepadButtonPressed(gamepad, button as i32)
+        }
+    }
+
+    pub fn is_gamepad_button_down(&self, gamepad: i32, button: GamepadButton) -> bool {
+        unsafe {
+            ffi::IsGamepadButtonDown(gamepad, button as i32)
+        }
+    }
+
+    pub fn is_gamepad_button_released(&self, gamepad: i32, button: GamepadButton) -> bool {
+        unsafe {
+            ffi::IsGamepadButtonReleased(gamepad, button as i32)
+        }
+    }
+
+    pub fn is_gamepad_button_up(&self, gamepad: i32, button: GamepadButton) -> bool {
+        unsafe {
+            ffi::IsGamepadButtonUp(gamepad, button as i32)
+        }
+    }
+
+    pub fn get_gamepad_axis_movement(&self, gamepad: i32, axis: GamepadAxis) -> f32 {
+        unsafe {
+            ffi::GetGamepadAxisMovement(gamepad, axis as i32)
+        }
+    }
+
+    /// Escape closes the window by default. Pass `Key::Null` to free it up for menus.
+    pub fn set_exit_key(&self, key: Key) {
+        unsafe {
+            ffi::SetExitKey(key as i32);
+        }
+    }
+}
+
+impl sealed::Sealed for RaylibHandle {}
+
+impl RaylibWindow for RaylibHandle {}
+
+impl Drop for RaylibHandle {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::CloseWindow();
+        }
+
+        WINDOW_OPEN.store(false, Ordering::SeqCst);
+    }
+}
+
+fn measure_c_text(text: &CStr, font_size: i32) -> i32 {
+    unsafe {
+        ffi::MeasureText(text.as_ptr() as *const libc::c_char, font_size)
+    }
+}
+
+fn measure_c_text_ex(font: &Font, text: &CStr, font_size: f32, spacing: f32) -> Vector2 {
+    let c_font = font.raw.clone();
+
+    unsafe {
+        ffi::MeasureTextEx(c_font, text.as_ptr(), font_size, spacing)
+    }
+}
+
+/// A frame being drawn. Calls EndDrawing, or EndTextureMode when drawing into
+/// a render texture, when dropped.
+///
+/// Generated drawing functions come from the `RaylibDraw` trait; the methods
+/// below take precedence over them. Derefs to the `RaylibHandle` it was begun
+/// on, so window and input functions stay available while drawing.
+pub struct DrawHandle<'a> {
+    target: DrawTarget,
+    handle: &'a RaylibHandle,
+}
+
+enum DrawTarget {
+    Screen,
+    Texture,
+    Mode2D,
+    Shader,
+    Blend,
+    Scissor,
+}
+
+impl sealed::Sealed for DrawHandle<'_> {}
+
+impl RaylibDraw for DrawHandle<'_> {}
+
+impl ops::Deref for DrawHandle<'_> {
+    type Target = RaylibHandle;
+
+    fn deref(&self) -> &RaylibHandle {
+        self.handle
+    }
+}
+
+impl DrawHandle<'_> {
+    /// Draws through `camera` until the returned handle is dropped.
+    pub fn begin_mode_2d(&mut self, camera: &Camera2D) -> DrawHandle<'_> {
+        unsafe {
+            ffi::BeginMode2D(camera.clone());
+        }
+
+        DrawHandle {
+            target: DrawTarget::Mode2D,
+            handle: self.handle,
+        }
+    }
+
+    pub fn clear_background(&mut self, color: &Color) {
+        let c_color = color.clone();
+
+        unsafe {
+            ffi::ClearBackground(c_color);
+        }
+    }
+
+    /// Strips NUL bytes from `text`.
+    pub fn draw_text<T: ToCStr + ?Sized>(&mut self, text: &T, x: i32, y: i32, font_size: i32, color: &Color) {
+        self.draw_c_text(&text.to_c_str_lossy(), x, y, font_size, color);
+    }
+
+    pub fn try_draw_text<T: ToCStr + ?Sized>(&mut self, text: &T, x: i32, y: i32, font_size: i32, color: &Color) -> Result<(), Error> {
+        self.draw_c_text(&text.to_c_str()?, x, y, font_size, color);
+
+        Ok(())
+    }
+
+    fn draw_c_text(&mut self, text: &CStr, x: i32, y: i32, font_size: i32, color: &Color) {
+        let c_color = color.clone();
+
+        unsafe {
+            ffi::DrawText(text.as_ptr() as *const libc::c_char, x, y, font_size, c_color);
+        }
+    }
+
+    /// Draws through `shader` until the returned handle is dropped.
+    pub fn begin_shader_mode(&mut self, shader: &Shader) -> DrawHandle<'_> {
+        unsafe {
+            ffi::BeginShaderMode(shader.raw.clone());
+        }
+
+        DrawHandle {
+            target: DrawTarget::Shader,
+            handle: self.handle,
+        }
+    }
+
+    /// Blends with `mode` until the returned handle is dropped.
+    pub fn begin_blend_mode(&mut self, mode: BlendMode) -> DrawHandle<'_> {
+        unsafe {
+            ffi::BeginBlendMode(mode as i32);
+        }
+
+        DrawHandle {
+            target: DrawTarget::Blend,
+            handle: self.handle,
+        }
+    }
+
+    /// Only draws inside `x`, `y`, `width`, `height` until the returned handle is dropped.
+    pub fn begin_scissor_mode(&mut self, x: i32, y: i32, width: i32, height: i32) -> DrawHandle<'_> {
+        unsafe {
+            ffi::BeginScissorMode(x, y, width, height);
+        }
+
+        DrawHandle {
+            target: DrawTarget::Scissor,
+            handle: self.handle,
+        }
+    }
+
+    /// Connected line segments through `points`.
+    pub fn draw_line_strip(&mut self, points: &[Vector2], color: &Color) {
+        let c_color = color.clone();
+
+        unsafe {
+            ffi::DrawLineStrip(points.as_ptr() as *mut Vector2, points.len() as i32, c_color);
+        }
+    }
+
+    /// A filled convex polygon, `points` wound counter-clockwise around `points[0]`.
+    pub fn draw_triangle_fan(&mut self, points: &[Vector2], color: &Color) {
+        let c_color = color.clone();
+
+        unsafe {
+            ffi::DrawTriangleFan(points.as_ptr() as *mut Vector2, points.len() as i32, c_color);
+        }
+    }
+
+    /// Each point after the second forms a triangle with the two before it.
+    pub fn draw_triangle_strip(&mut self, points: &[Vector2], color: &Color) {
+        let c_color = color.clone();
+
+        unsafe {
+            ffi::DrawTriangleStrip(points.as_ptr() as *mut Vector2, points.len() as i32, c_color);
+        }
+    }
+
+    /// Draws the color buffer of `target` upright, stretched over `dest`.
+    pub fn draw_render_texture(&mut self, target: &RenderTexture, dest: &Rectangle, tint: &Color) {
+        let c_texture = target.raw.texture.clone();
+        let c_source = target.bounds();
+        let c_dest = dest.clone();
+        let c_tint = tint.clone();
+
+        unsafe {
+            ffi::DrawTexturePro(c_texture, c_source, c_dest, Vector2::default(), 0.0, c_tint);
+        }
+    }
+
+    /// Strips NUL bytes from `text`.
+    pub fn draw_text_ex<T: ToCStr + ?Sized>(&mut self, font: &Font, text: &T, position: &Vector2, font_size: f32, spacing: f32, tint: &Color) {
+        self.draw_c_text_ex(font, &text.to_c_str_lossy(), position, font_size, spacing, tint);
+    }
+
+    pub fn try_draw_text_ex<T: ToCStr + ?Sized>(&mut self, font: &Font, text: &T, position: &Vector2, font_size: f32, spacing: f32, tint: &Color) -> Result<(), Error> {
+        self.draw_c_text_ex(font, &text.to_c_str()?, position, font_size, spacing, tint);
+
+        Ok(())
+    }
+
+    fn draw_c_text_ex(&mut self, font: &Font, text: &CStr, position: &Vector2, font_size: f32, spacing: f32, tint: &Color) {
+        let c_font = font.raw.clone();
+        let c_position = *position;
+        let c_tint = tint.clone();
+
+        unsafe {
+            ffi::DrawTextEx(c_font, text.as_ptr(), c_position, font_size, spacing, c_tint);
+        }
+    }
+
+    pub fn draw_circle_v(&mut self, center: &Vector2, radius: f32, color: &Color) {
+        let c_center = *center;
+        let c_color = color.clone();
+
+        unsafe {
+            ffi::DrawCircleV(c_center, radius, c_color);
+        }
+    }
+
+    pub fn draw_rectangle(&mut self, x: i32, y: i32, width: i32, height: i32, color: &Color) {
+        let c_color = color.clone();
+
+        unsafe {
+            ffi::DrawRectangle(x, y, width, height, c_color);
+        }
+    }
+
+    pub fn draw_texture(&mut self, texture: &Texture, x: i32, y: i32, tint: &Color) {
+        let c_texture = texture.raw.clone();
+        let c_tint = tint.clone();
+
+        unsafe {
+            ffi::DrawTexture(c_texture, x, y, c_tint);
+        }
+    }
+
+    pub fn draw_texture_v(&mut self, texture: &Texture, position: &Vector2, tint: &Color) {
+        let c_texture = texture.raw.clone();
+        let c_position = *position;
+        let c_tint = tint.clone();
+
+        unsafe {
+            ffi::DrawTextureV(c_texture, c_position, c_tint);
+        }
+    }
+
+    pub fn draw_texture_rec(&mut self, texture: &Texture, source: &Rectangle, position: &Vector2, tint: &Color) {
+        let c_texture = texture.raw.clone();
+        let c_source = source.clone();
+        let c_position = *position;
+        let c_tint = tint.clone();
+
+        unsafe {
+            ffi::DrawTextureRec(c_texture, c_source, c_position, c_tint);
+        }
+    }
+
+    /// Draws `source` of the texture stretched over `dest`, rotated in degrees around `origin`,
+    /// which is relative to the top-left corner of `dest`.
+    pub fn draw_texture_pro(
+        &mut self,
+        texture: &Texture,
+        source: &Rectangle,
+        dest: &Rectangle,
+        origin: &Vector2,
+        rotation: f32,
+        tint: &Color,
+    ) {
+        let c_texture = texture.raw.clone();
+        let c_source = source.clone();
+        let c_dest = dest.clone();
+        let c_origin = *origin;
+        let c_tint = tint.clone();
+
+        unsafe {
+            ffi::DrawTexturePro(c_texture, c_source, c_dest, c_origin, rotation, c_tint);
+        }
+    }
+
+    /// Strips NUL bytes from `text`.
+    pub fn gui_button<T: ToCStr + ?Sized>(&mut self, rect: &Rectangle, text: &T) -> bool {
+        gui_c_button(rect, &text.to_c_str_lossy())
+    }
+
+    pub fn try_gui_button<T: ToCStr + ?Sized>(&mut self, rect: &Rectangle, text: &T) -> Result<bool, Error> {
+        Ok(gui_c_button(rect, &text.to_c_str()?))
+    }
+}
+
+fn gui_c_button(rect: &Rectangle, text: &CStr) -> bool {
+    let c_rect = rect.clone();
+
+    unsafe {
+        GuiButton(c_rect, text.as_ptr() as *const libc::c_char)
+    }
+}
+
+impl Drop for DrawHandle<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            match self.target {
+                DrawTarget::Screen => ffi::EndDrawing(),
+                DrawTarget::Texture => ffi::EndTextureMode(),
+                DrawTarget::Mode2D => ffi::EndMode2D(),
+                DrawTarget::Shader => ffi::EndShaderMode(),
+                DrawTarget::Blend => ffi::EndBlendMode(),
+                DrawTarget::Scissor => ffi::EndScissorMode(),
+            }
+        }
+    }
+}
+
+pub fn check_collision_circle_rec(circle: &Circle, rec: &Rectangle) -> bool {
+    let c_center = circle.center;
+    let c_rec = rec.clone();
+
+    unsafe {
+        ffi::CheckCollisionCircleRec(c_center, circle.radius, c_rec)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::Vector2;
+
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    const EPSILON: f32 = 0.0001;
+
+    fn assert_near(actual: Vector2, expected: Vector2) {
+        assert!(
+            (actual.x - expected.x).abs() < EPSILON && (actual.y - expected.y).abs() < EPSILON,
+            "{:?} is not close to {:?}", actual, expected,
+        );
+    }
+
+    #[test]
+    fn normalized_keeps_zero_vector() {
+        let normalized = Vector2::default().normalized();
+
+        assert!(!normalized.x.is_nan() && !normalized.y.is_nan());
+        assert_eq!(normalized, Vector2::default());
+    }
+
+    #[test]
+    fn normalized_has_unit_length() {
+        let normalized = Vector2 { x: 3.0, y: 4.0 }.normalized();
+
+        assert_near(normalized, Vector2 { x: 0.6, y: 0.8 });
+    }
+
+    #[test]
+    fn angle_of_parallel_vectors_is_zero() {
+        let a = Vector2 { x: 0.1, y: 0.3 };
+        let b = a * 7.0;
+
+        let angle = Vector2::angle(&a, &b);
+
+        assert!(!angle.is_nan());
+        assert!(angle.abs() < EPSILON);
+    }
+
+    #[test]
+    fn angle_of_antiparallel_vectors_is_pi() {
+        let a = Vector2 { x: 0.1, y: 0.3 };
+        let b = -a * 7.0;
+
+        let angle = Vector2::angle(&a, &b);
+
+        assert!(!angle.is_nan());
+        assert!((angle - PI).abs() < EPSILON);
+    }
+
+    #[test]
+    fn angle_with_zero_vector_is_zero() {
+        assert_eq!(Vector2::angle(&Vector2 { x: 1.0, y: 0.0 }, &Vector2::default()), 0.0);
+    }
+
+    #[test]
+    fn reflect_mirrors_about_normal() {
+        let direction = Vector2 { x: 1.0, y: 1.0 };
+        let normal = Vector2 { x: 0.0, y: -1.0 };
+
+        assert_near(direction.reflect(&normal), Vector2 { x: 1.0, y: -1.0 });
+    }
+
+    #[test]
+    fn rotate_by_quarter_turn() {
+        let rotated = Vector2 { x: 1.0, y: 0.0 }.rotate(FRAC_PI_2);
+
+        assert_near(rotated, Vector2 { x: 0.0, y: 1.0 });
+    }
+
+    #[test]
+    fn clamp_length_below_max_is_unchanged() {
+        let vector = Vector2 { x: 3.0, y: 4.0 };
+
+        assert_eq!(vector.clamp_length(10.0), vector);
+    }
+
+    #[test]
+    fn clamp_length_above_max_is_shortened() {
+        let clamped = Vector2 { x: 3.0, y: 4.0 }.clamp_length(2.5);
+
+        assert_near(clamped, Vector2 { x: 1.5, y: 2.0 });
+    }
+
+    #[test]
+    fn clamp_length_to_negative_max_is_zero() {
+        assert_eq!(Vector2 { x: 3.0, y: 4.0 }.clamp_length(-1.0), Vector2::default());
+    }
+
+    #[test]
+    fn dot_of_perpendicular_vectors_is_zero() {
+        assert_eq!(Vector2 { x: 2.0, y: 3.0 }.dot(&Vector2 { x: -3.0, y: 2.0 }), 0.0);
+    }
+
+    #[test]
+    fn cross_is_positive_clockwise_on_screen() {
+        assert_eq!(Vector2 { x: 1.0, y: 0.0 }.cross(&Vector2 { x: 0.0, y: 1.0 }), 1.0);
+    }
+
+    #[test]
+    fn distance_between_points() {
+        assert_eq!(Vector2 { x: 1.0, y: 1.0 }.distance(&Vector2 { x: 4.0, y: 5.0 }), 5.0);
+    }
+
+    #[test]
+    fn lerp_ends_at_both_vectors() {
+        let a = Vector2 { x: -2.0, y: 5.0 };
+        let b = Vector2 { x: 4.0, y: 1.0 };
+
+        assert_near(a.lerp(&b, 0.0), a);
+        assert_near(a.lerp(&b, 1.0), b);
+    }
+
+    #[test]
+    fn operators() {
+        let a = Vector2 { x: 6.0, y: -4.0 };
+        let b = Vector2 { x: 1.0, y: 2.0 };
+
+        assert_eq!(a - b, Vector2 { x: 5.0, y: -6.0 });
+        assert_eq!(-a, Vector2 { x: -6.0, y: 4.0 });
+        assert_eq!(a / 2.0, Vector2 { x: 3.0, y: -2.0 });
+
+        let mut c = a;
+        c += b;
+        assert_eq!(c, Vector2 { x: 7.0, y: -2.0 });
+
+        let mut c = a;
+        c -= b;
+        assert_eq!(c, Vector2 { x: 5.0, y: -6.0 });
+
+        let mut c = a;
+        c *= 0.5;
+        assert_eq!(c, Vector2 { x: 3.0, y: -2.0 });
+
+        let mut c = a;
+        c /= 2.0;
+        assert_eq!(c, Vector2 { x: 3.0, y: -2.0 });
     }
 }
@@ -0,0 +1,279 @@
+use crate::raylib::{
+    Camera2D, Color, Rectangle, Vector2,
+    DrawHandle, Font, RaylibHandle, Texture, ToCStr,
+    RaylibDraw,
+};
+
+/// The drawing calls game code makes, so it can draw to the screen through a
+/// `DrawHandle` or into a `Recorder` without a window.
+pub trait Renderer {
+    /// What `draw_mode_2d` draws through, such as the `DrawHandle` guard from `begin_mode_2d`.
+    type Mode2D<'a>: Renderer where Self: 'a;
+
+    fn clear_background(&mut self, color: &Color);
+
+    fn draw_rectangle_rec(&mut self, rec: &Rectangle, color: &Color);
+
+    fn draw_circle_v(&mut self, center: &Vector2, radius: f32, color: &Color);
+
+    fn draw_text<T: ToCStr + ?Sized>(&mut self, text: &T, x: i32, y: i32, font_size: i32, color: &Color);
+
+    fn draw_text_ex<T: ToCStr + ?Sized>(&mut self, font: &Font, text: &T, position: &Vector2, font_size: f32, spacing: f32, tint: &Color);
+
+    fn draw_texture_pro(
+        &mut self,
+        texture: &Texture,
+        source: &Rectangle,
+        dest: &Rectangle,
+        origin: &Vector2,
+        rotation: f32,
+        tint: &Color,
+    );
+
+    /// Returns true when the button was clicked this frame.
+    fn gui_button<T: ToCStr + ?Sized>(&mut self, bounds: &Rectangle, text: &T) -> bool;
+
+    fn measure_text<T: ToCStr + ?Sized>(&self, text: &T, font_size: i32) -> i32;
+
+    fn measure_text_ex<T: ToCStr + ?Sized>(&self, font: &Font, text: &T, font_size: f32, spacing: f32) -> Vector2;
+
+    /// Runs `draw` with everything drawn through `camera`.
+    fn draw_mode_2d<F: FnOnce(&mut Self::Mode2D<'_>)>(&mut self, camera: &Camera2D, draw: F);
+}
+
+impl Renderer for DrawHandle<'_> {
+    type Mode2D<'a> = DrawHandle<'a> where Self: 'a;
+
+    fn clear_background(&mut self, color: &Color) {
+        DrawHandle::clear_background(self, color);
+    }
+
+    fn draw_rectangle_rec(&mut self, rec: &Rectangle, color: &Color) {
+        RaylibDraw::draw_rectangle_rec(self, rec, color);
+    }
+
+    fn draw_circle_v(&mut self, center: &Vector2, radius: f32, color: &Color) {
+        DrawHandle::draw_circle_v(self, center, radius, color);
+    }
+
+    fn draw_text<T: ToCStr + ?Sized>(&mut self, text: &T, x: i32, y: i32, font_size: i32, color: &Color) {
+        DrawHandle::draw_text(self, text, x, y, font_size, color);
+    }
+
+    fn draw_text_ex<T: ToCStr + ?Sized>(&mut self, font: &Font, text: &T, position: &Vector2, font_size: f32, spacing: f32, tint: &Color) {
+        DrawHandle::draw_text_ex(self, font, text, position, font_size, spacing, tint);
+    }
+
+    fn draw_texture_pro(
+        &mut self,
+        texture: &Texture,
+        source: &Rectangle,
+        dest: &Rectangle,
+        origin: &Vector2,
+        rotation: f32,
+        tint: &Color,
+    ) {
+        DrawHandle::draw_texture_pro(self, texture, source, dest, origin, rotation, tint);
+    }
+
+    fn gui_button<T: ToCStr + ?Sized>(&mut self, bounds: &Rectangle, text: &T) -> bool {
+        DrawHandle::gui_button(self, bounds, text)
+    }
+
+    fn measure_text<T: ToCStr + ?Sized>(&self, text: &T, font_size: i32) -> i32 {
+        RaylibHandle::measure_text(self, text, font_size)
+    }
+
+    fn measure_text_ex<T: ToCStr + ?Sized>(&self, font: &Font, text: &T, font_size: f32, spacing: f32) -> Vector2 {
+        RaylibHandle::measure_text_ex(self, font, text, font_size, spacing)
+    }
+
+    fn draw_mode_2d<F: FnOnce(&mut DrawHandle<'_>)>(&mut self, camera: &Camera2D, draw: F) {
+        draw(&mut self.begin_mode_2d(camera));
+    }
+}
+
+/// One drawing call captured by a `Recorder`. Text is stored as a `String`,
+/// with its top-left corner as `position`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCommand {
+    ClearBackground(Color),
+    Rectangle {
+        rec: Rectangle,
+        color: Color,
+    },
+    Circle {
+        center: Vector2,
+        radius: f32,
+        color: Color,
+    },
+    Text {
+        text: String,
+        position: Vector2,
+        font_size: f32,
+        color: Color,
+    },
+    Texture {
+        dest: Rectangle,
+        tint: Color,
+    },
+    Button {
+        bounds: Rectangle,
+        text: String,
+    },
+    BeginMode2D {
+        offset: Vector2,
+        target: Vector2,
+        rotation: f32,
+        zoom: f32,
+    },
+    EndMode2D,
+}
+
+/// A `Renderer` that draws nothing and keeps a list of what it was asked to draw,
+/// for checking frames without a window or GPU.
+///
+/// Text is measured as if every glyph were `font_size / 2` wide, plus the
+/// spacing between glyphs.
+#[derive(Default)]
+pub struct Recorder {
+    commands: Vec<DrawCommand>,
+    /// Labels of buttons that report a click.
+    clicked: Vec<String>,
+}
+
+impl Recorder {
+    pub fn new() -> Recorder {
+        Recorder::default()
+    }
+
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    /// Every piece of text drawn, in order.
+    pub fn texts(&self) -> impl Iterator<Item = &str> {
+        self.commands.iter().filter_map(|command| match command {
+            DrawCommand::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Makes buttons labeled `text` report a click from now on.
+    pub fn click(&mut self, text: &str) {
+        self.clicked.push(text.to_string());
+    }
+
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+}
+
+fn to_string<T: ToCStr + ?Sized>(text: &T) -> String {
+    text.to_c_str_lossy().to_string_lossy().into_owned()
+}
+
+fn recorded_width(text: &str, font_size: f32, spacing: f32) -> f32 {
+    let glyphs = text.chars().count() as f32;
+
+    if glyphs == 0.0 {
+        return 0.0;
+    }
+
+    glyphs * font_size / 2.0 + (glyphs - 1.0) * spacing
+}
+
+impl Renderer for Recorder {
+    type Mode2D<'a> = Recorder;
+
+    fn clear_background(&mut self, color: &Color) {
+        self.commands.push(DrawCommand::ClearBackground(color.clone()));
+    }
+
+    fn draw_rectangle_rec(&mut self, rec: &Rectangle, color: &Color) {
+        self.commands.push(DrawCommand::Rectangle {
+            rec: rec.clone(),
+            color: color.clone(),
+        });
+    }
+
+    fn draw_circle_v(&mut self, center: &Vector2, radius: f32, color: &Color) {
+        self.commands.push(DrawCommand::Circle {
+            center: *center,
+            radius,
+            color: color.clone(),
+        });
+    }
+
+    fn draw_text<T: ToCStr + ?Sized>(&mut self, text: &T, x: i32, y: i32, font_size: i32, color: &Color) {
+        self.commands.push(DrawCommand::Text {
+            text: to_string(text),
+            position: Vector2 { x: x as f32, y: y as f32 },
+            font_size: font_size as f32,
+            color: color.clone(),
+        });
+    }
+
+    fn draw_text_ex<T: ToCStr + ?Sized>(&mut self, _font: &Font, text: &T, position: &Vector2, font_size: f32, _spacing: f32, tint: &Color) {
+        self.commands.push(DrawCommand::Text {
+            text: to_string(text),
+            position: *position,
+            font_size,
+            color: tint.clone(),
+        });
+    }
+
+    fn draw_texture_pro(
+        &mut self,
+        _texture: &Texture,
+        _source: &Rectangle,
+        dest: &Rectangle,
+        _origin: &Vector2,
+        _rotation: f32,
+        tint: &Color,
+    ) {
+        self.commands.push(DrawCommand::Texture {
+            dest: dest.clone(),
+            tint: tint.clone(),
+        });
+    }
+
+    fn gui_button<T: ToCStr + ?Sized>(&mut self, bounds: &Rectangle, text: &T) -> bool {
+        let text = to_string(text);
+        let clicked = self.clicked.contains(&text);
+
+        self.commands.push(DrawCommand::Button {
+            bounds: bounds.clone(),
+            text,
+        });
+
+        clicked
+    }
+
+    fn measure_text<T: ToCStr + ?Sized>(&self, text: &T, font_size: i32) -> i32 {
+        // raylib spaces the default font by a tenth of its size.
+        let spacing = (font_size / 10).max(1) as f32;
+
+        recorded_width(&to_string(text), font_size as f32, spacing) as i32
+    }
+
+    fn measure_text_ex<T: ToCStr + ?Sized>(&self, _font: &Font, text: &T, font_size: f32, spacing: f32) -> Vector2 {
+        Vector2 {
+            x: recorded_width(&to_string(text), font_size, spacing),
+            y: font_size,
+        }
+    }
+
+    fn draw_mode_2d<F: FnOnce(&mut Recorder)>(&mut self, camera: &Camera2D, draw: F) {
+        self.commands.push(DrawCommand::BeginMode2D {
+            offset: camera.offset,
+            target: camera.target,
+            rotation: camera.rotation,
+            zoom: camera.zoom,
+        });
+
+        draw(self);
+
+        self.commands.push(DrawCommand::EndMode2D);
+    }
+}
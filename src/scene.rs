@@ -0,0 +1,586 @@
+use crate::raylib::{
+    DrawHandle, RaylibHandle, RaylibWindow, Image, Texture,
+    Key,
+    flush_draw_batch,
+};
+use crate::canvas::Canvas;
+use crate::capture::Capture;
+use crate::clock::{Time, DeltaTime, GameClock};
+use crate::transition::{Transition, PlayingTransition};
+
+/// What a scene wants after a frame. Transitions `SceneManager` cannot follow,
+/// such as `GoTo` an index it does not have, are ignored.
+///
+/// From an overlay, `Ended` pops it, and the transitions between scenes close
+/// every overlay before they apply.
+pub enum SceneResult {
+    OnGoing,
+    /// On to the next scene, or quit after the last one.
+    Ended,
+    GoTo(usize),
+    /// `GoTo` the scene added under this name.
+    GoToNamed(String),
+    /// Runs `init` on the current scene again.
+    Restart,
+    /// Returns to the scene that was running before this one.
+    Back,
+    Quit,
+    /// Opens an overlay over the current scene, such as a pause menu.
+    Push(Box<dyn Scene>),
+    /// Closes the top overlay.
+    Pop,
+}
+
+/// A screen of the game. `SceneManager` calls `update` on the top scene at a fixed
+/// rate, as many times as a frame needs, then `draw` once per frame on it and the
+/// scenes under it.
+///
+/// Scenes that update and draw in a single call are run through a `FrameAdapter`.
+pub trait Scene {
+    /// Resets the scene, each time it is switched to or restarted. Runs before any
+    /// transition into the scene, which draws it.
+    fn init(&mut self) {}
+    /// Reads the frame's input, once per frame before its updates, on the scene that
+    /// updates. A frame can run several updates or none, so key and button presses
+    /// are read here and kept for the next `update`.
+    fn handle_input(&mut self, _rl: &RaylibHandle) {}
+    /// Steps the scene forward. Held keys and other input state can be read from `rl`.
+    /// `delta_time.unscaled` is always the `SceneManager` timestep, `delta_time.scaled`
+    /// that after its `GameClock`.
+    fn update(&mut self, rl: &RaylibHandle, delta_time: DeltaTime) -> SceneResult;
+    /// `alpha` in `0.0..1.0` is how far the frame is between the last update and the
+    /// next, for blending moving things between their last two positions. It is `1.0`
+    /// for scenes under an overlay, which do not update.
+    fn draw(&mut self, d: &mut DrawHandle, alpha: f32);
+    /// The scene starts running, after `init` and any transition into it, or
+    /// after being pushed.
+    fn on_enter(&mut self) {}
+    /// The scene stops running: it was switched away from, restarted, popped,
+    /// or the game is quitting.
+    fn on_exit(&mut self) {}
+    /// An overlay was pushed over the scene. It keeps being drawn but no longer updates.
+    fn on_pause(&mut self) {}
+    /// The overlay over the scene was popped.
+    fn on_resume(&mut self) {}
+    /// The window changed size, in screen pixels. A `Canvas` keeps its own size.
+    fn on_window_resize(&mut self, _width: i32, _height: i32) {}
+}
+
+/// A scene that updates and draws in one call, which suits raygui, where a button
+/// is drawn and clicked at once.
+pub trait FrameScene {
+    fn init(&mut self) {}
+    fn frame(&mut self, d: &mut DrawHandle, delta_time: f32) -> SceneResult;
+    /// Draws the scene without updating it, while an overlay is on top.
+    fn draw(&mut self, d: &mut DrawHandle);
+}
+
+/// Runs a `FrameScene` as a `Scene`. `update` only adds up the time and the frame
+/// runs in `draw`, once per frame whatever the number of updates, so a transition
+/// comes out of the following `update`.
+///
+/// The time is unscaled: frame scenes are menus and other UI, which keep going at
+/// full speed through slow motion or a paused `GameClock`.
+pub struct FrameAdapter<S> {
+    scene: S,
+    delta_time: f32,
+    /// Between `on_enter` and `on_exit`, and not under an overlay. `draw` only runs a
+    /// frame while running, so a scene cannot be clicked through a transition or overlay.
+    running: bool,
+    result: Option<SceneResult>,
+}
+
+impl<S: FrameScene> FrameAdapter<S> {
+    pub fn new(scene: S) -> Self {
+        Self {
+            scene,
+            delta_time: 0.0,
+            running: false,
+            result: None,
+        }
+    }
+
+    pub fn scene(&self) -> &S {
+        &self.scene
+    }
+
+    pub fn scene_mut(&mut self) -> &mut S {
+        &mut self.scene
+    }
+}
+
+impl<S: FrameScene> Scene for FrameAdapter<S> {
+    fn init(&mut self) {
+        self.delta_time = 0.0;
+        self.running = false;
+        self.result = None;
+        self.scene.init();
+    }
+
+    fn update(&mut self, _rl: &RaylibHandle, delta_time: DeltaTime) -> SceneResult {
+        self.delta_time += delta_time.unscaled;
+
+        self.result.take().unwrap_or(SceneResult::OnGoing)
+    }
+
+    fn draw(&mut self, d: &mut DrawHandle, _alpha: f32) {
+        // No frame runs while a transition is waiting, or it could be lost to another one.
+        if !self.running || self.result.is_some() {
+            return self.scene.draw(d);
+        }
+
+        let result = self.scene.frame(d, self.delta_time);
+        self.delta_time = 0.0;
+
+        if !matches!(result, SceneResult::OnGoing) {
+            self.result = Some(result);
+        }
+    }
+
+    fn on_enter(&mut self) {
+        self.running = true;
+        self.delta_time = 0.0;
+    }
+
+    fn on_exit(&mut self) {
+        self.running = false;
+    }
+
+    fn on_pause(&mut self) {
+        self.running = false;
+    }
+
+    fn on_resume(&mut self) {
+        self.running = true;
+        self.delta_time = 0.0;
+    }
+}
+
+pub struct SceneManager {
+    scenes: Vec<Box<dyn Scene>>,
+    /// Name of each scene in `scenes`, at the same index.
+    names: Vec<String>,
+    current_scene: usize,
+    /// Scenes left for another one, most recent last, for `SceneResult::Back`.
+    history: Vec<usize>,
+    /// Scenes pushed over the current one, top last. Only the top one updates.
+    overlays: Vec<Box<dyn Scene>>,
+    canvas: Option<Canvas>,
+    capture: Option<Capture>,
+    /// Seconds of game time per update.
+    timestep: f32,
+    transition: Option<Transition>,
+    /// The transition into the current scene, while it plays.
+    playing: Option<PlayingTransition>,
+    /// Scales the time of every scene.
+    clock: GameClock,
+    frame_stepping: bool,
+}
+
+/// Updates a frame may run to catch up. Past that, a slow frame slows the game
+/// down instead of making the next frame slower still.
+const MAX_UPDATES_PER_FRAME: u32 = 8;
+
+impl SceneManager {
+    pub fn new() -> Self {
+        Self {
+            scenes: Vec::new(),
+            names: Vec::new(),
+            current_scene: 0,
+            history: Vec::new(),
+            overlays: Vec::new(),
+            canvas: None,
+            capture: None,
+            timestep: 1.0 / 60.0,
+            transition: None,
+            playing: None,
+            clock: GameClock::default(),
+            frame_stepping: false,
+        }
+    }
+
+    /// Adds `scene` after the others, to be found by `name` too. Panics if a scene
+    /// was already added under `name`.
+    pub fn add(&mut self, name: &str, scene: Box<dyn Scene>) {
+        assert!(self.index_of(name).is_none(), "a scene named \"{}\" was already added", name);
+
+        self.scenes.push(scene);
+        self.names.push(name.to_string());
+    }
+
+    /// Names of the scenes, in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Index of the scene added under `name`.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|scene_name| scene_name == name)
+    }
+
+    /// The scene `run` starts with. Ignored if there is no scene at `index`.
+    pub fn set(&mut self, index: usize) {
+        if index < self.scenes.len() {
+            self.current_scene = index;
+        }
+    }
+
+    /// Like `set`, by name. Returns false, leaving the scene as it was, if there
+    /// is no scene called `name`.
+    pub fn set_named(&mut self, name: &str) -> bool {
+        match self.index_of(name) {
+            Some(index) => {
+                self.current_scene = index;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Scenes draw into `canvas` instead of straight to the window.
+    pub fn set_canvas(&mut self, canvas: Canvas) {
+        self.canvas = Some(canvas);
+    }
+
+    /// Seconds of game time each update steps over, `1.0 / 60.0` by default.
+    /// Ignored unless positive.
+    pub fn set_timestep(&mut self, timestep: f32) {
+        if timestep > 0.0 {
+            self.timestep = timestep;
+        }
+    }
+
+    pub fn timestep(&self) -> f32 {
+        self.timestep
+    }
+
+    /// Plays `transition` whenever the scene changes. It has no effect without a
+    /// positive duration.
+    pub fn set_transition(&mut self, transition: Transition) {
+        self.transition = Some(transition).filter(|transition| transition.duration > 0.0);
+    }
+
+    /// The global clock, whose time scale and pause apply to every scene.
+    pub fn clock(&self) -> &GameClock {
+        &self.clock
+    }
+
+    pub fn clock_mut(&mut self) -> &mut GameClock {
+        &mut self.clock
+    }
+
+    /// Enables the debug keys: F7 pauses and resumes the global clock, F8 steps a
+    /// single update while it is paused.
+    pub fn set_frame_stepping(&mut self, enabled: bool) {
+        self.frame_stepping = enabled;
+    }
+
+    /// Enables the screenshot and GIF hotkeys.
+    pub fn set_capture(&mut self, capture: Capture) {
+        self.capture = Some(capture);
+    }
+
+    /// Runs scenes until one quits, the last one ends or the window is closed.
+    pub fn run(&mut self, rl: &mut RaylibHandle) {
+        if self.current_scene >= self.scenes.len() {
+            return;
+        }
+
+        let scene = &mut self.scenes[self.current_scene];
+        scene.init();
+        scene.on_enter();
+
+        let mut time = Time::default();
+        let timestep = self.timestep;
+        // Frame time the scenes have not been updated for yet.
+        let mut accumulator = 0.0;
+
+        loop {
+            let delta_time = time.delta_time();
+            accumulator += delta_time.min(timestep * MAX_UPDATES_PER_FRAME as f32);
+
+            // Before updating, so the mouse is already mapped onto the canvas.
+            if let Some(canvas) = &mut self.canvas {
+                canvas.update(rl);
+            }
+
+            if rl.is_window_resized() {
+                let (width, height) = (rl.get_screen_width(), rl.get_screen_height());
+
+                self.scenes[self.current_scene].on_window_resize(width, height);
+
+                for overlay in &mut self.overlays {
+                    overlay.on_window_resize(width, height);
+                }
+            }
+
+            if self.frame_stepping {
+                if rl.is_key_pressed(Key::F7) {
+                    let paused = self.clock.is_paused();
+                    self.clock.set_paused(!paused);
+                }
+
+                if rl.is_key_pressed(Key::F8) {
+                    self.clock.step();
+                }
+            }
+
+            let mut scene_result = SceneResult::OnGoing;
+
+            // Nothing updates while a transition plays, so input is ignored.
+            if let Some(playing) = &mut self.playing {
+                playing.update(delta_time);
+                accumulator = 0.0;
+
+                if playing.finished() {
+                    self.playing = None;
+                    self.scenes[self.current_scene].on_enter();
+                }
+            }
+
+            if self.playing.is_none() {
+                self.top_scene().handle_input(rl);
+            }
+
+            while self.playing.is_none() && accumulator >= timestep {
+                accumulator -= timestep;
+                let delta_time = self.clock.advance(DeltaTime::new(timestep));
+                scene_result = self.top_scene().update(rl, delta_time);
+
+                // Anything else changes which scene updates next.
+                if !matches!(scene_result, SceneResult::OnGoing) {
+                    break;
+                }
+            }
+
+            let alpha = match scene_result {
+                SceneResult::OnGoing => accumulator / timestep,
+                _ => 1.0,
+            };
+
+            // The last frame of a scene about to change, for the transition to start from.
+            let needs_snapshot = self.transition.is_some() && match &scene_result {
+                SceneResult::OnGoing | SceneResult::Push(_) | SceneResult::Pop | SceneResult::Quit => false,
+                SceneResult::Ended => self.overlays.is_empty(),
+                _ => true,
+            };
+            let mut snapshot = None;
+
+            let scene = &mut self.scenes[self.current_scene];
+            let overlays = &mut self.overlays;
+            let playing = &self.playing;
+
+            match &mut self.canvas {
+                Some(canvas) => {
+                    let (width, height) = (canvas.width(), canvas.height());
+
+                    {
+                        let mut d = rl.begin_texture_mode(canvas.target_mut());
+
+                        draw(scene, overlays, &mut d, alpha);
+
+                        if let Some(playing) = playing {
+                            playing.draw(&mut d, width, height);
+                        }
+                    }
+
+                    if needs_snapshot {
+                        snapshot = Image::from_render_texture(canvas.target()).and_then(|image| Texture::from_image(&image));
+                    }
+
+                    canvas.post_process(rl);
+
+                    let mut d = rl.begin_drawing();
+                    canvas.draw(&mut d);
+
+                    if let Some(capture) = &mut self.capture {
+                        capture.frame(&mut d, delta_time);
+                    }
+                },
+                None => {
+                    let (width, height) = (rl.get_screen_width(), rl.get_screen_height());
+
+                    let mut d = rl.begin_drawing();
+                    draw(scene, overlays, &mut d, alpha);
+
+                    if let Some(playing) = playing {
+                        playing.draw(&mut d, width, height);
+                    }
+
+                    if needs_snapshot {
+                        flush_draw_batch();
+                        snapshot = Image::from_screen().and_then(|image| Texture::from_image(&image));
+                    }
+
+                    if let Some(capture) = &mut self.capture {
+                        capture.frame(&mut d, delta_time);
+                    }
+                },
+            }
+
+            if rl.window_should_close() {
+                break;
+            }
+
+            let scene_result = match scene_result {
+                SceneResult::Push(mut overlay) => {
+                    self.top_scene().on_pause();
+
+                    overlay.init();
+                    overlay.on_enter();
+                    self.overlays.push(overlay);
+                    continue;
+                },
+                SceneResult::Pop => {
+                    self.pop_overlay();
+                    continue;
+                },
+                SceneResult::Ended if !self.overlays.is_empty() => {
+                    self.pop_overlay();
+                    continue;
+                },
+                SceneResult::OnGoing => continue,
+                scene_result => scene_result,
+            };
+
+            // Resolved before anything is torn down, so a transition that cannot be
+            // followed leaves the overlays and history as they were.
+            let next_scene = match &scene_result {
+                SceneResult::OnGoing | SceneResult::Push(_) | SceneResult::Pop => continue,
+                SceneResult::Ended => {
+                    if self.current_scene + 1 >= self.scenes.len() {
+                        break;
+                    }
+
+                    self.current_scene + 1
+                },
+                SceneResult::GoTo(index) => {
+                    if *index >= self.scenes.len() {
+                        continue;
+                    }
+
+                    *index
+                },
+                SceneResult::GoToNamed(name) => match self.index_of(name) {
+                    Some(index) => index,
+                    None => continue,
+                },
+                SceneResult::Restart => self.current_scene,
+                SceneResult::Back => match self.history.last() {
+                    Some(&index) => index,
+                    None => continue,
+                },
+                SceneResult::Quit => break,
+            };
+
+            while !self.overlays.is_empty() {
+                self.pop_overlay();
+            }
+
+            match scene_result {
+                SceneResult::Restart => {},
+                SceneResult::Back => {
+                    self.history.pop();
+                },
+                _ => self.history.push(self.current_scene),
+            }
+
+            self.scenes[self.current_scene].on_exit();
+
+            self.current_scene = next_scene;
+
+            let scene = &mut self.scenes[self.current_scene];
+            scene.init();
+
+            // Without a snapshot there is nothing to transition from, so the scene just starts.
+            match (&self.transition, snapshot) {
+                (Some(transition), Some(snapshot)) => {
+                    self.playing = Some(PlayingTransition::new(transition.clone(), snapshot));
+                },
+                _ => scene.on_enter(),
+            }
+
+            time = Time::default();
+            accumulator = 0.0;
+        }
+
+        while !self.overlays.is_empty() {
+            self.pop_overlay();
+        }
+
+        // A scene still transitioning in was never entered.
+        if self.playing.take().is_none() {
+            self.scenes[self.current_scene].on_exit();
+        }
+    }
+
+    /// The topmost overlay, or the current scene without one. The only scene updating.
+    fn top_scene(&mut self) -> &mut Box<dyn Scene> {
+        match self.overlays.last_mut() {
+            Some(overlay) => overlay,
+            None => &mut self.scenes[self.current_scene],
+        }
+    }
+
+    /// Closes the top overlay and resumes the scene under it.
+    fn pop_overlay(&mut self) {
+        if let Some(mut overlay) = self.overlays.pop() {
+            overlay.on_exit();
+            self.top_scene().on_resume();
+        }
+    }
+}
+
+/// Draws `scene`, then every overlay over it from the bottom up. Only the top one
+/// is drawn at `alpha`, the others have not moved since their last update.
+fn draw(scene: &mut Box<dyn Scene>, overlays: &mut [Box<dyn Scene>], d: &mut DrawHandle, alpha: f32) {
+    let (top, below) = match overlays.split_last_mut() {
+        Some(split) => split,
+        None => return scene.draw(d, alpha),
+    };
+
+    scene.draw(d, 1.0);
+
+    for overlay in below {
+        overlay.draw(d, 1.0);
+    }
+
+    top.draw(d, alpha);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Scene, SceneManager, SceneResult};
+    use crate::clock::DeltaTime;
+    use crate::raylib::{DrawHandle, RaylibHandle};
+
+    struct Empty;
+
+    impl Scene for Empty {
+        fn update(&mut self, _rl: &RaylibHandle, _delta_time: DeltaTime) -> SceneResult {
+            SceneResult::OnGoing
+        }
+
+        fn draw(&mut self, _d: &mut DrawHandle, _alpha: f32) {}
+    }
+
+    #[test]
+    fn scenes_are_found_by_name() {
+        let mut scene_manager = SceneManager::new();
+        scene_manager.add("menu", Box::new(Empty));
+        scene_manager.add("level", Box::new(Empty));
+
+        assert_eq!(scene_manager.index_of("level"), Some(1));
+        assert_eq!(scene_manager.index_of("credits"), None);
+        assert!(scene_manager.set_named("menu"));
+        assert!(!scene_manager.set_named("credits"));
+    }
+
+    #[test]
+    #[should_panic(expected = "already added")]
+    fn adding_a_name_twice_panics() {
+        let mut scene_manager = SceneManager::new();
+        scene_manager.add("level", Box::new(Empty));
+        scene_manager.add("level", Box::new(Empty));
+    }
+}
@@ -0,0 +1,95 @@
+use std::f32::consts::TAU;
+
+use crate::raylib::{
+    Sound, Wave,
+    is_audio_device_ready,
+};
+
+const SAMPLE_RATE: u32 = 22050;
+
+pub enum Effect {
+    Paddle,
+    Brick,
+    Goal,
+}
+
+/// Sound effects for gameplay events. Every effect is silent when there is no audio device.
+pub struct Sounds {
+    paddle: Option<Sound>,
+    brick: Option<Sound>,
+    goal: Option<Sound>,
+}
+
+impl Sounds {
+    pub fn load() -> Sounds {
+        if !is_audio_device_ready() {
+            return Sounds::silent();
+        }
+
+        Sounds {
+            paddle: load_blip(440.0, 0.08),
+            brick: load_blip(660.0, 0.12),
+            goal: load_blip(220.0, 0.4),
+        }
+    }
+
+    /// Plays nothing. Needs no audio device.
+    pub fn silent() -> Sounds {
+        Sounds {
+            paddle: None,
+            brick: None,
+            goal: None,
+        }
+    }
+
+    pub fn play(&self, effect: Effect) {
+        let sound = match effect {
+            Effect::Paddle => &self.paddle,
+            Effect::Brick => &self.brick,
+            Effect::Goal => &self.goal,
+        };
+
+        if let Some(sound) = sound {
+            sound.play();
+        }
+    }
+}
+
+fn load_blip(frequency: f32, duration: f32) -> Option<Sound> {
+    Sound::from_wave(&Wave::load_from_memory(".wav", &blip(frequency, duration))?)
+}
+
+/// A sine tone fading out linearly, encoded as a 16-bit mono WAV file.
+/// The game ships without audio assets, so effects are synthesized.
+fn blip(frequency: f32, duration: f32) -> Vec<u8> {
+    let samples = (SAMPLE_RATE as f32 * duration) as u32;
+    let data_size = samples * 2;
+
+    let mut wav = Vec::with_capacity(44 + data_size as usize);
+
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // Mono
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // Bytes per second
+    wav.extend_from_slice(&2u16.to_le_bytes()); // Bytes per frame
+    wav.extend_from_slice(&16u16.to_le_bytes()); // Bits per sample
+
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_size.to_le_bytes());
+
+    for i in 0..samples {
+        let time = i as f32 / SAMPLE_RATE as f32;
+        let fade = 1.0 - i as f32 / samples as f32;
+        let sample = (time * frequency * TAU).sin() * fade * 0.5;
+
+        wav.extend_from_slice(&((sample * i16::MAX as f32) as i16).to_le_bytes());
+    }
+
+    wav
+}
@@ -0,0 +1,194 @@
+use crate::raylib::{
+    Color, Rectangle, Vector2,
+    DrawHandle, Texture,
+    RaylibDraw,
+    WHITE,
+};
+
+/// How one scene gives way to the next, played by `SceneManager` after the outgoing
+/// scene's `on_exit`. That scene shows as a still of its last frame.
+///
+/// Cross-fades, slides and wipes uncover the incoming scene as they play, so it is
+/// drawn throughout and its `init` runs before the transition rather than after.
+/// It only enters, with `on_enter`, once the transition ends, and does not update
+/// or take input until then.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    pub effect: TransitionEffect,
+    /// Seconds.
+    pub duration: f32,
+    pub easing: Easing,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransitionEffect {
+    /// Fades out to the color during the first half, then in from it.
+    Fade(Color),
+    /// Fades the outgoing scene out over the incoming one.
+    CrossFade,
+    /// Slides the outgoing scene off towards the direction, uncovering the incoming one.
+    Slide(Direction),
+    /// Sweeps an edge towards the direction, with the incoming scene behind it.
+    Wipe(Direction),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// Maps linear progress in `0.0..=1.0` to eased progress, quadratically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    /// Starts slow.
+    EaseIn,
+    /// Ends slow.
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+                }
+            },
+        }
+    }
+}
+
+/// A `Transition` in progress, run by `SceneManager`.
+pub(crate) struct PlayingTransition {
+    transition: Transition,
+    /// Last frame of the outgoing scene.
+    snapshot: Texture,
+    elapsed: f32,
+}
+
+impl PlayingTransition {
+    pub(crate) fn new(transition: Transition, snapshot: Texture) -> PlayingTransition {
+        PlayingTransition {
+            transition,
+            snapshot,
+            elapsed: 0.0,
+        }
+    }
+
+    pub(crate) fn update(&mut self, delta_time: f32) {
+        self.elapsed += delta_time;
+    }
+
+    pub(crate) fn finished(&self) -> bool {
+        self.elapsed >= self.transition.duration
+    }
+
+    /// Draws the effect over the incoming scene, in a `width` x `height` target.
+    pub(crate) fn draw(&self, d: &mut DrawHandle, width: i32, height: i32) {
+        let progress = self.transition.easing.apply(self.elapsed / self.transition.duration);
+        let (width, height) = (width as f32, height as f32);
+        let screen = Rectangle { x: 0.0, y: 0.0, width, height };
+
+        let source = self.snapshot.bounds();
+        // Snapshot pixels per target pixel, as a screen read back on a high DPI display is larger.
+        let scale = Vector2 { x: source.width / width, y: source.height / height };
+
+        match &self.transition.effect {
+            TransitionEffect::Fade(color) => {
+                let opacity = if progress < 0.5 {
+                    self.draw_snapshot(d, &source, &screen, &WHITE);
+                    progress * 2.0
+                } else {
+                    (1.0 - progress) * 2.0
+                };
+
+                d.draw_rectangle_rec(&screen, &with_opacity(color, opacity));
+            },
+            TransitionEffect::CrossFade => {
+                self.draw_snapshot(d, &source, &screen, &with_opacity(&WHITE, 1.0 - progress));
+            },
+            TransitionEffect::Slide(direction) => {
+                let dest = match direction {
+                    Direction::Left => Rectangle { x: -width * progress, ..screen.clone() },
+                    Direction::Right => Rectangle { x: width * progress, ..screen.clone() },
+                    Direction::Up => Rectangle { y: -height * progress, ..screen.clone() },
+                    Direction::Down => Rectangle { y: height * progress, ..screen.clone() },
+                };
+
+                self.draw_snapshot(d, &source, &dest, &WHITE);
+            },
+            TransitionEffect::Wipe(direction) => {
+                // The part of the outgoing scene the edge has not reached yet.
+                let dest = match direction {
+                    Direction::Left => Rectangle { width: width * (1.0 - progress), ..screen.clone() },
+                    Direction::Right => Rectangle { x: width * progress, width: width * (1.0 - progress), ..screen.clone() },
+                    Direction::Up => Rectangle { height: height * (1.0 - progress), ..screen.clone() },
+                    Direction::Down => Rectangle { y: height * progress, height: height * (1.0 - progress), ..screen.clone() },
+                };
+                let source = Rectangle {
+                    x: dest.x * scale.x,
+                    y: dest.y * scale.y,
+                    width: dest.width * scale.x,
+                    height: dest.height * scale.y,
+                };
+
+                self.draw_snapshot(d, &source, &dest, &WHITE);
+            },
+        }
+    }
+
+    fn draw_snapshot(&self, d: &mut DrawHandle, source: &Rectangle, dest: &Rectangle, tint: &Color) {
+        d.draw_texture_pro(&self.snapshot, source, dest, &Vector2::default(), 0.0, tint);
+    }
+}
+
+fn with_opacity(color: &Color, opacity: f32) -> Color {
+    Color {
+        a: (color.a as f32 * opacity.clamp(0.0, 1.0)) as u8,
+        ..color.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Easing;
+
+    const EASINGS: [Easing; 4] = [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut];
+
+    #[test]
+    fn easings_start_at_0_and_end_at_1() {
+        for easing in EASINGS {
+            assert_eq!(easing.apply(0.0), 0.0, "{:?}", easing);
+            assert_eq!(easing.apply(1.0), 1.0, "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn easings_at_half_way() {
+        assert_eq!(Easing::Linear.apply(0.5), 0.5);
+        assert_eq!(Easing::EaseIn.apply(0.5), 0.25);
+        assert_eq!(Easing::EaseOut.apply(0.5), 0.75);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn easings_clamp_out_of_range_progress() {
+        for easing in EASINGS {
+            assert_eq!(easing.apply(-0.5), 0.0, "{:?}", easing);
+            assert_eq!(easing.apply(1.5), 1.0, "{:?}", easing);
+            assert_eq!(easing.apply(f32::INFINITY), 1.0, "{:?}", easing);
+        }
+    }
+}